@@ -7,24 +7,33 @@ use std::cell::RefCell;
 pub struct AlphaZeroPlayer<
     G: Game,
     M: Module<
-        Tensor<(Const<{ G::CHANNELS }>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
+        Tensor<(usize, Const<{ G::CHANNELS }>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
         Output = (
-            Tensor<(Const<{ G::TOTAL_MOVES }>,), f32, AutoDevice>,
-            Tensor<(Const<1>,), f32, AutoDevice>,
+            Tensor<(usize, Const<{ G::TOTAL_MOVES }>), f32, AutoDevice>,
+            Tensor<(usize, Const<1>), f32, AutoDevice>,
         ),
         Error = <AutoDevice as HasErr>::Err,
     >,
 > {
     pub mcts: RefCell<MCTS<G, M>>,
+    move_override: Option<Box<dyn MoveOverride<G>>>,
+}
+
+/*
+Takes over from the search in positions it knows how to play better, such as an exactly solved endgame.
+Returning None leaves the move to MCTS.
+ */
+pub trait MoveOverride<G: Game> {
+    fn override_move(&self, game: &G) -> Option<G::Move>;
 }
 
 impl<
         G: Game,
         M: Module<
-            Tensor<(Const<{ G::CHANNELS }>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
+            Tensor<(usize, Const<{ G::CHANNELS }>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
             Output = (
-                Tensor<(Const<{ G::TOTAL_MOVES }>,), f32, AutoDevice>,
-                Tensor<(Const<1>,), f32, AutoDevice>,
+                Tensor<(usize, Const<{ G::TOTAL_MOVES }>), f32, AutoDevice>,
+                Tensor<(usize, Const<1>), f32, AutoDevice>,
             ),
             Error = <AutoDevice as HasErr>::Err,
         >,
@@ -33,6 +42,7 @@ impl<
     pub fn new(model: M, temperature: f32, training: bool, traversal_iter: usize) -> Self {
         Self {
             mcts: MCTS::new(G::new(), model, temperature, training, traversal_iter).into(),
+            move_override: None,
         }
     }
 
@@ -59,6 +69,22 @@ impl<
                 traversal_iter,
             )
             .into(),
+            move_override: None,
+        }
+    }
+
+    // For models trained on canonical positions, see MCTS::with_canonical
+    pub fn with_canonical(self, canonical: bool) -> Self {
+        Self {
+            mcts: self.mcts.into_inner().with_canonical(canonical).into(),
+            move_override: self.move_override,
+        }
+    }
+
+    pub fn with_override(self, move_override: impl MoveOverride<G> + 'static) -> Self {
+        Self {
+            move_override: Some(Box::new(move_override)),
+            ..self
         }
     }
 }
@@ -66,16 +92,25 @@ impl<
 impl<
         G: Game + 'static,
         M: Module<
-                Tensor<(Const<{ G::CHANNELS }>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
+                Tensor<(usize, Const<{ G::CHANNELS }>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
                 Output = (
-                    Tensor<(Const<{ G::TOTAL_MOVES }>,), f32, AutoDevice>,
-                    Tensor<(Const<1>,), f32, AutoDevice>,
+                    Tensor<(usize, Const<{ G::TOTAL_MOVES }>), f32, AutoDevice>,
+                    Tensor<(usize, Const<1>), f32, AutoDevice>,
                 ),
                 Error = <AutoDevice as HasErr>::Err,
             > + 'static,
     > Player<G> for AlphaZeroPlayer<G, M>
 {
     fn choose_move(&self, game: &G) -> Result<<G as Game>::Move, rust_games_shared::PlayerError> {
+        // The tree is rebuilt from the new position on the next search, since it never saw this move
+        if let Some(mv) = self
+            .move_override
+            .as_ref()
+            .and_then(|move_override| move_override.override_move(game))
+        {
+            return Ok(mv);
+        }
+
         let mv = self.mcts.borrow_mut().choose_move(game);
 
         //println!("the v-value of the board is now: {}", self.mcts.borrow_mut().root.get_mut().v);
@@ -87,6 +122,14 @@ impl<
         self.mcts.get_mut().reset_board();
     }
 
+    // The search's visit shares, so a fresh search is run for each call
+    fn policy(&self, game: &G) -> Option<Vec<(G::Move, f32)>> {
+        if game.is_over() {
+            return None;
+        }
+        Some(self.mcts.borrow_mut().analyze(game).0)
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -97,8 +140,9 @@ mod tests {
     use super::AlphaZeroPlayer;
     use crate::{BoardGameModel, MCTS};
     use dfdx::prelude::*;
-    use rust_games_games::Othello;
-    use rust_games_shared::{Game, Player};
+    use rust_games_games::{DotsAndBoxes, Othello};
+    use rust_games_shared::{Game, GameResult, Player, PlayerId};
+    use std::cmp::Ordering;
 
     #[test]
     fn first_move() {
@@ -141,4 +185,40 @@ mod tests {
         g.make_move(m.unwrap());
         g.print();
     }
+
+    // Flattens the 4 channels of the 5x5 lattice straight into the 12 edge policy and a value
+    type TinyDotsModel = (Flatten2D, SplitInto<(Linear<100, 12>, (Linear<100, 1>, Tanh))>);
+
+    #[test]
+    fn extra_turns() {
+        // Completing a box gives the same player another move, so players
+        // must cope with several moves being made between their turns
+        type G = DotsAndBoxes<2, 2>;
+        let dev: AutoDevice = Default::default();
+        let nn = dev.build_module::<TinyDotsModel, f32>();
+        let first = AlphaZeroPlayer::<G, _>::new(nn.clone(), 1.0, false, 10);
+        let second = AlphaZeroPlayer::<G, _>::new(nn, 1.0, false, 10);
+
+        let mut g = G::new();
+        let mut plies = 0;
+        while !g.is_over() {
+            let player = match g.current_player() {
+                PlayerId::First => &first,
+                PlayerId::Second => &second,
+            };
+            g.make_move(player.choose_move(&g).unwrap());
+            plies += 1;
+        }
+
+        // Every one of the 12 edges gets drawn, and the boxes decide the result
+        assert_eq!(plies, 12);
+        let (first_boxes, second_boxes) = g.scores();
+        assert_eq!(first_boxes + second_boxes, 4);
+        let result = g.get_result().unwrap();
+        match first_boxes.cmp(&second_boxes) {
+            Ordering::Greater => assert!(matches!(result, GameResult::Winner(PlayerId::First))),
+            Ordering::Less => assert!(matches!(result, GameResult::Winner(PlayerId::Second))),
+            Ordering::Equal => assert!(matches!(result, GameResult::Tie)),
+        }
+    }
 }
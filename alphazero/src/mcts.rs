@@ -1,7 +1,7 @@
 use crate::{nn::load_from_file, UnfinishedTrainingExample};
 use dfdx::prelude::*;
 use rand::{distributions::WeightedIndex, prelude::Distribution, thread_rng};
-use rust_games_shared::{Game, GameResult, PlayerError, PlayerId};
+use rust_games_shared::{Game, GameResult, NnEncoder, PlayerError, PlayerId};
 use std::{cell::Cell, collections::HashMap};
 
 #[derive(Clone, Debug)]
@@ -16,6 +16,49 @@ where [(); G::TOTAL_MOVES]: Sized{
     children: Vec<Self>,
 }
 
+/*
+Runs the model on a batch of positions in one forward pass, giving each one's move priors and its value
+from PlayerId::First's perspective.
+A canonical model sees each position from the mover's side and values it for the mover,
+so its value is flipped back when the second player is to move. The tree itself always stores values for First.
+ */
+fn evaluate<G: Game>(
+    model: &impl Module<
+        Tensor<(usize, Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
+        Output = (
+            Tensor<(usize, Const<{G::TOTAL_MOVES}>), f32, AutoDevice>,
+            Tensor<(usize, Const<1>), f32, AutoDevice>,
+        ),
+        Error = <AutoDevice as HasErr>::Err,
+    >,
+    encoder: &mut NnEncoder<G, AutoDevice>,
+    games: &[G],
+    canonical: bool,
+) -> Vec<([f32; G::TOTAL_MOVES], f32)>
+where
+    Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>: Sized,
+    [(); G::TOTAL_MOVES]: Sized,
+{
+    if games.is_empty() {
+        return vec![];
+    }
+
+    let (p, v) = model.forward(encoder.encode_batch(games, canonical));
+    let (p, v) = (p.as_vec(), v.as_vec());
+    games
+        .iter()
+        .zip(p.chunks_exact(G::TOTAL_MOVES))
+        .zip(v)
+        .map(|((game, priors), value)| {
+            let value = match (canonical, game.current_player()) {
+                (true, PlayerId::Second) => -value,
+                _ => value,
+            };
+            (priors.try_into().unwrap(), value)
+        })
+        .collect()
+}
+
 impl<G: Game> ActionNode<G>
 where
     Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>: Sized,
@@ -60,6 +103,24 @@ where
         }
     }
 
+    /*
+    At chance nodes (e.g. dice rolls), the next child to examine is drawn from the game's known
+    outcome probabilities instead, so the averaged q-value estimates the expected value.
+     */
+    fn sample_chance_child(&mut self) -> Option<(&mut ActionNode<G>, usize)> {
+        let probs: HashMap<G::Move, f32> = self.post_state.chance_outcomes().into_iter().collect();
+        let weights: Vec<f32> = self
+            .children
+            .iter()
+            .map(|child| *probs.get(&child.action.unwrap()).unwrap_or(&0.0))
+            .collect();
+
+        let dist = WeightedIndex::new(weights).ok()?;
+        let chosen_index = dist.sample(&mut thread_rng());
+
+        Some((&mut self.children[chosen_index], chosen_index))
+    }
+
     /*
     Chooses the next child to examine during the MCTS traversal.
     This is calculated using exponentiated visit count, described [here](https://gwern.net/doc/reinforcement-learning/model/alphago/2017-silver.pdf#page=8).
@@ -85,28 +146,34 @@ where
     fn spawn_children(
         &mut self,
         model: &impl Module<
-                Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
+                Tensor<(usize, Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
                 Output = (
-                    Tensor<(Const<{G::TOTAL_MOVES}>,), f32, AutoDevice>,
-                    Tensor<(Const<1>,), f32, AutoDevice>,
+                    Tensor<(usize, Const<{G::TOTAL_MOVES}>), f32, AutoDevice>,
+                    Tensor<(usize, Const<1>), f32, AutoDevice>,
                 ),
                 Error = <AutoDevice as HasErr>::Err,
             >,
+        encoder: &mut NnEncoder<G, AutoDevice>,
+        canonical: bool,
     ) {
         if !self.children.is_empty(){
             return;
         }
 
         let next_actions = self.post_state.legal_moves();
+        let subgames: Vec<G> = next_actions
+            .iter()
+            .map(|action| {
+                let mut subgame = self.post_state.clone();
+                subgame.make_move(*action);
+                subgame
+            })
+            .collect();
 
-        for action in next_actions {
-            let mut subgame = self.post_state.clone();
-            subgame.make_move(action);
-
-            let (p, v) = model.forward(subgame.to_nn_input());
-            let mut v_value = v.array()[0];
+        // All the children are evaluated together, in one batch
+        let evaluations = evaluate(model, encoder, &subgames, canonical);
 
-            
+        for ((action, subgame), (p, mut v_value)) in next_actions.into_iter().zip(subgames).zip(evaluations) {
             // If the new node is terminal, set v to the value associated with the winning player
             // Rather than the model's guess, which would just estimate this anyways
             if subgame.is_over() {
@@ -131,7 +198,7 @@ where
                 q: 0.0,
                 n: 0,
                 v: v_value,
-                p: p.array(),
+                p,
                 children: vec![],
             };
 
@@ -139,21 +206,27 @@ where
         }
     }
 
-    pub fn to_unfinished_example(&self) -> UnfinishedTrainingExample<G> {
-        let position = self.post_state.to_nn_input();
+    pub fn to_unfinished_example(&self, canonical: bool) -> UnfinishedTrainingExample<G> {
         let next_move_probs = self.children
             .iter()
             .map(|node| (node.action.expect("Child must have an associated action"), node.n))
             .collect();
-        UnfinishedTrainingExample::new(position, next_move_probs)
+        let mut position = vec![0.0; G::nn_input_len()];
+        if canonical {
+            self.post_state.write_canonical_nn_input(&mut position);
+            UnfinishedTrainingExample::new_canonical(position, next_move_probs, self.post_state.current_player())
+        } else {
+            self.post_state.write_nn_input(&mut position);
+            UnfinishedTrainingExample::new(position, next_move_probs)
+        }
     }
 }
 
 pub struct MCTS<G: Game, M: Module<
-            Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
+            Tensor<(usize, Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
             Output = (
-                Tensor<(Const<{G::TOTAL_MOVES}>,), f32, AutoDevice>,
-                Tensor<(Const<1>,), f32, AutoDevice>,
+                Tensor<(usize, Const<{G::TOTAL_MOVES}>), f32, AutoDevice>,
+                Tensor<(usize, Const<1>), f32, AutoDevice>,
             ),
             Error = <AutoDevice as HasErr>::Err,
         >> {
@@ -162,14 +235,17 @@ pub struct MCTS<G: Game, M: Module<
     pub temperature: f32,
     pub train_examples: Option<Vec<UnfinishedTrainingExample<G>>>,
     pub traverse_iter: usize,
+    // Whether the model reads canonical positions and gives values for the player to move
+    pub canonical: bool,
+    encoder: NnEncoder<G, AutoDevice>,
     index_map: HashMap<G::Move, usize>
 }
 
 impl<'a, G: Game, M: Module<
-            Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
+            Tensor<(usize, Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
             Output = (
-                Tensor<(Const<{G::TOTAL_MOVES}>,), f32, AutoDevice>,
-                Tensor<(Const<1>,), f32, AutoDevice>,
+                Tensor<(usize, Const<{G::TOTAL_MOVES}>), f32, AutoDevice>,
+                Tensor<(usize, Const<1>), f32, AutoDevice>,
             ),
             Error = <AutoDevice as HasErr>::Err,
         >> MCTS<G, M>
@@ -191,9 +267,12 @@ where
 
                 // Find a new traversal path, from the root all the way to a leaf node
                 while !current.children.is_empty() {
-                    (current, index) = current
-                        .best_child_traversal(&self.index_map)
-                        .expect("There should be a child of current node!");
+                    let next = if current.post_state.is_chance_node() {
+                        current.sample_chance_child()
+                    } else {
+                        current.best_child_traversal(&self.index_map)
+                    };
+                    (current, index) = next.expect("There should be a child of current node!");
                     path.push(index);
                 }
 
@@ -202,7 +281,7 @@ where
 
                 //Expand leaf node at non-terminal state
                 if !current.post_state.is_over() {
-                    current.spawn_children(&self.model);
+                    current.spawn_children(&self.model, &mut self.encoder, self.canonical);
                 }
             }
 
@@ -220,38 +299,50 @@ where
         }
     }
 
-    pub fn choose_move(&mut self, game: &G) -> Result<G::Move, PlayerError> {
-        // First, update on opponent's move (if applicable)
+    // Moves the root to the given position, keeping the subtree below it if it was already searched
+    fn update_root(&mut self, game: &G) {
         let root = self.root.get_mut();
         if game.get_board() != root.post_state.get_board() {
             // If the game board is a child of this state:
             // Use that subtree as the root
             // But first, make sure children are spawned
-            root.spawn_children(&self.model);
-            let mut found = None;
-            for child in self.root.get_mut().children.iter() {
-                if child.post_state.get_board() == game.get_board() {
-                    found = Some(child);
-                }
-            }
+            root.spawn_children(&self.model, &mut self.encoder, self.canonical);
+            let found = self
+                .root
+                .get_mut()
+                .children
+                .iter()
+                .find(|child| child.post_state.get_board() == game.get_board())
+                .cloned();
 
             // Change the root node to the current game
-            // (Which is a child of the old root)
-            self.root = Cell::new(
-                found
-                    .expect(
-                        "The game given as input is not one move after the game stored in the MCTS",
-                    )
-                    .clone(),
-            );
-            // Else:
-            // Should maybe use the new game as a new root
-            // But for now, panic
+            // (Which is usually a child of the old root)
+            // If the game has moved further than that, e.g. because the opponent completed
+            // a box and moved again, start a fresh tree from the given position
+            let new_root = match found {
+                Some(child) => child,
+                None => Self::new_root(&self.model, &mut self.encoder, game.clone(), self.canonical),
+            };
+            self.root = Cell::new(new_root);
         }
+    }
+
+    pub fn choose_move(&mut self, game: &G) -> Result<G::Move, PlayerError> {
+        // First, update on opponent's move (if applicable)
+        self.update_root(game);
         self.traverse(self.traverse_iter);
         let r = self.root.get_mut();
+
+        // Nature moves at chance nodes, so there is no policy to learn and the outcome is drawn by its probability
+        if r.post_state.is_chance_node() {
+            let (child, _) = r.sample_chance_child().ok_or(PlayerError::NoLegalMoves)?;
+            let action = child.action.unwrap();
+            *r = child.clone();
+            return Ok(action);
+        }
+
         if let Some(examples) = &mut self.train_examples {
-            let ex = r.to_unfinished_example();
+            let ex = r.to_unfinished_example(self.canonical);
             examples.push(ex);
         }
     
@@ -268,6 +359,28 @@ where
         }
     }
 
+    /*
+    Searches the position without playing a move, giving the share of the visits each move got
+    and the position's value from PlayerId::First's perspective.
+    Chance nodes have no policy, only their expected value.
+     */
+    pub fn analyze(&mut self, game: &G) -> (Vec<(G::Move, f32)>, f32) {
+        self.update_root(game);
+        self.traverse(self.traverse_iter);
+
+        let root = self.root.get_mut();
+        if root.post_state.is_chance_node() {
+            return (vec![], root.q);
+        }
+        let visits: usize = root.children.iter().map(|child| child.n).sum();
+        let shares = root
+            .children
+            .iter()
+            .map(|child| (child.action.unwrap(), child.n as f32 / visits.max(1) as f32))
+            .collect();
+        (shares, root.q)
+    }
+
     pub fn new(
         root: G,
         model: M,
@@ -275,8 +388,20 @@ where
         training: bool,
         traverse_iter: usize
     ) -> Self {
-        let (p, v) = model.forward(root.to_nn_input());
+        Self::new_on_device(root, model, temperature, training, traverse_iter, &Default::default())
+    }
+
+    // Like new, with positions encoded on the given device, which should be the model's
+    pub fn new_on_device(
+        root: G,
+        model: M,
+        temperature: f32,
+        training: bool,
+        traverse_iter: usize,
+        dev: &AutoDevice,
+    ) -> Self {
         let train_examples = if training {Some(vec![])} else {None};
+        let mut encoder = NnEncoder::new(dev);
         let mut index_map = HashMap::new();
 
         for (i, action) in G::all_possible_moves().iter().enumerate() {
@@ -284,30 +409,47 @@ where
         }
 
         Self {
-            root: Cell::new(ActionNode {
-                action: None,
-                post_state: root,
-                q: 0.0,
-                n: 0,
-                v: v.array()[0],
-                p: p.array(),
-                children: vec![],
-            }),
+            root: Cell::new(Self::new_root(&model, &mut encoder, root, false)),
             model: model,
             temperature: temperature,
             train_examples,
             traverse_iter,
+            canonical: false,
+            encoder,
             index_map
         }
     }
 
+    /*
+    Switches the search to a model trained on canonical positions (see Game::to_canonical_nn_input).
+     */
+    pub fn with_canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        let game = self.root.get_mut().post_state.clone();
+        self.root = Cell::new(Self::new_root(&self.model, &mut self.encoder, game, canonical));
+        self
+    }
+
+    fn new_root(model: &M, encoder: &mut NnEncoder<G, AutoDevice>, game: G, canonical: bool) -> ActionNode<G> {
+        let (p, v) = evaluate(model, encoder, std::slice::from_ref(&game), canonical)[0];
+        ActionNode {
+            action: None,
+            post_state: game,
+            q: 0.0,
+            n: 0,
+            v,
+            p,
+            children: vec![],
+        }
+    }
+
     pub fn new_from_file<B: BuildOnDevice<AutoDevice, f32, Built = M>>(root: G, temperature: f32, file_name: &str, dev: &AutoDevice, training: bool, traverse_iter: usize) -> Self
     where M: TensorCollection<f32, AutoDevice>,
         [(); G::TOTAL_MOVES]: Sized
         {
             let model = load_from_file::<G, B>(file_name, dev);
         
-            Self::new(root, model, temperature, training, traverse_iter)
+            Self::new_on_device(root, model, temperature, training, traverse_iter, dev)
     }
 
     pub fn save_nn(&self, model_name: &str, data_dir: &str) 
@@ -319,21 +461,88 @@ where
     }
 
     pub fn reset_board(&mut self) {
-        let g = G::new();
-        let (p, v) = self.model.forward(g.to_nn_input());
-        self.root.replace(ActionNode {
-            action: None,
-            post_state: g,
-            q: 0.0,
-            n: 0,
-            v: v.array()[0],
-            p: p.array(),
-            children: vec![],
-        });
+        let root = Self::new_root(&self.model, &mut self.encoder, G::new(), self.canonical);
+        self.root.replace(root);
     }
 }
 
 pub struct MCTSConfig {
     pub traversal_iter: usize,
     pub temperature: f32,
+    pub canonical: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MCTS;
+    use dfdx::prelude::*;
+    use rust_games_games::{Backgammon, TicTacToe};
+    use rust_games_shared::{Game, PlayerId};
+    use std::collections::HashMap;
+
+    // Flattens the position straight into the policy and value, much cheaper than the full network
+    type TinyModel<const IN: usize, const MOVES: usize> =
+        (Flatten2D, SplitInto<(Linear<IN, MOVES>, (Linear<IN, 1>, Tanh))>);
+
+    #[test]
+    fn canonical_values_are_stored_for_first() {
+        let dev: AutoDevice = Default::default();
+        // Values every position at tanh(1) for whoever the model thinks it is valuing
+        let mut nn = dev.build_module::<TinyModel<27, 9>, f32>();
+        nn.1 .0 .1 .0.weight.fill_with_zeros();
+        nn.1 .0 .1 .0.bias.fill_with_ones();
+
+        let mut g = TicTacToe::new();
+        g.make_move(g.legal_moves()[0]);
+        assert_eq!(g.current_player(), PlayerId::Second);
+
+        let mut absolute = MCTS::new(g.clone(), nn.clone(), 1.0, false, 1);
+        assert!(absolute.root.get_mut().v > 0.0);
+
+        // A canonical model values the position for Second, so the tree stores the opposite for First
+        let mut canonical = MCTS::new(g, nn, 1.0, false, 1).with_canonical(true);
+        assert!(canonical.root.get_mut().v < 0.0);
+    }
+
+    #[test]
+    fn chance_children_follow_their_probabilities() {
+        let dev: AutoDevice = Default::default();
+        let nn = dev.build_module::<TinyModel<390, 172>, f32>();
+        // The opening roll is a chance node, with doubles half as likely as other rolls
+        let mut mcts = MCTS::new(Backgammon::new(), nn, 1.0, false, 1);
+        mcts.traverse(1);
+
+        let root = mcts.root.get_mut();
+        assert!(root.post_state.is_chance_node());
+        let probs: HashMap<_, _> = root.post_state.chance_outcomes().into_iter().collect();
+
+        let draws = 36_000;
+        let mut counts = HashMap::new();
+        for _ in 0..draws {
+            let (child, _) = root.sample_chance_child().unwrap();
+            *counts.entry(child.action.unwrap()).or_insert(0) += 1;
+        }
+
+        assert_eq!(counts.len(), probs.len());
+        for (roll, count) in counts {
+            let share = count as f32 / draws as f32;
+            assert!((share - probs[&roll]).abs() < 0.01, "{:?} drawn {} of the time", roll, share);
+        }
+    }
+
+    #[test]
+    fn chance_roots_give_no_example_or_policy() {
+        let dev: AutoDevice = Default::default();
+        let nn = dev.build_module::<TinyModel<390, 172>, f32>();
+        let g = Backgammon::new();
+        assert!(g.is_chance_node());
+
+        let (policy, _) = MCTS::new(g.clone(), nn.clone(), 1.0, false, 8).analyze(&g);
+        assert!(policy.is_empty());
+
+        let mut mcts = MCTS::new(g.clone(), nn, 1.0, true, 8);
+        let roll = mcts.choose_move(&g).unwrap();
+        assert!(g.chance_outcomes().iter().any(|(outcome, _)| *outcome == roll));
+        assert!(mcts.train_examples.unwrap().is_empty());
+    }
 }
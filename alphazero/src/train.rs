@@ -1,15 +1,18 @@
 use dfdx::{optim::Adam, prelude::*};
 use std::{collections::HashMap, vec};
 
-use rust_games_shared::Game;
+use rust_games_shared::{Game, NnEncoder, PlayerId};
 
 #[derive(Clone)]
 pub struct UnfinishedTrainingExample<G: Game>
 where
     Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>: Sized,
 {
-    position: Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
+    // Written by Game::write_nn_input, or write_canonical_nn_input
+    position: Vec<f32>,
     next_move_probs: Vec<(G::Move, usize)>,
+    // For canonical positions, the player to move, whose view the value target is given from
+    perspective: Option<PlayerId>,
 }
 
 impl<G: Game> UnfinishedTrainingExample<G>
@@ -17,16 +20,34 @@ where
     Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>: Sized,
 {
     pub fn new(
-        position: Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
+        position: Vec<f32>,
         next_move_probs: Vec<(G::Move, usize)>,
     ) -> Self {
         UnfinishedTrainingExample {
             position,
             next_move_probs,
+            perspective: None,
         }
     }
 
+    pub fn new_canonical(
+        position: Vec<f32>,
+        next_move_probs: Vec<(G::Move, usize)>,
+        to_move: PlayerId,
+    ) -> Self {
+        UnfinishedTrainingExample {
+            position,
+            next_move_probs,
+            perspective: Some(to_move),
+        }
+    }
+
+    // winner is from PlayerId::First's perspective, and is flipped for canonical examples where Second was to move
     pub fn finish(self, winner: f32) -> TrainingExample<G> {
+        let winner = match self.perspective {
+            Some(PlayerId::Second) => -winner,
+            _ => winner,
+        };
         TrainingExample {
             position: self.position,
             winner,
@@ -40,7 +61,7 @@ pub struct TrainingExample<G: Game>
 where
     Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>: Sized,
 {
-    pub position: Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
+    pub position: Vec<f32>,
     pub winner: f32,
     pub next_move_probs: Vec<(G::Move, usize)>,
 }
@@ -72,7 +93,7 @@ where
     }
 
     pub fn new(
-        position: Tensor<(Const<{G::CHANNELS}>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>,
+        position: Vec<f32>,
         winner: f32,
         next_move_probs: Vec<(G::Move, usize)>,
     ) -> Self {
@@ -92,11 +113,13 @@ fn loss<G: Game, VShape: Shape, PShape: Shape>(
     mse_loss + bce_loss
 }
 
+// Trains on the examples batch_accum at a time, with one batched forward pass and optimizer step per batch
 pub fn update_on_many<
-    G: Game,
+    G: Game + 'static,
     Model: ModuleMut<
             <Tensor<
                 (
+                    usize,
                     Const<{G::CHANNELS}>,
                     G::BoardSizeX,
                     G::BoardSizeY,
@@ -106,53 +129,33 @@ pub fn update_on_many<
             > as dfdx::tensor::Trace<f32, AutoDevice>>::Traced,
             Error = <AutoDevice as HasErr>::Err,
             Output = (
-                Tensor<(Const<{G::TOTAL_MOVES}>,), f32, AutoDevice, OwnedTape<f32, AutoDevice>>,
-                Tensor<(Const<1>,), f32, AutoDevice, OwnedTape<f32, AutoDevice>>,
+                Tensor<(usize, Const<{G::TOTAL_MOVES}>,), f32, AutoDevice, OwnedTape<f32, AutoDevice>>,
+                Tensor<(usize, Const<1>,), f32, AutoDevice, OwnedTape<f32, AutoDevice>>,
             ),
         > + TensorCollection<f32, AutoDevice>,
 >(
     model: &mut Model,
-    mut examples: Vec<&TrainingExample<G>>,
+    examples: Vec<&TrainingExample<G>>,
     opt: &mut Adam<Model, f32, AutoDevice>,
     batch_accum: usize,
     dev: &AutoDevice,
 ) -> Result<(), <AutoDevice as dfdx::tensor::HasErr>::Err>{
-    let mut grads = model.try_alloc_grads()?;
-    for (i, example) in examples.iter_mut().enumerate() {
-        let (p, v) = model.try_forward_mut(example.position.clone().traced(grads.clone()))?;
-        let loss: Tensor<(), f32, AutoDevice, OwnedTape<f32, AutoDevice>> = loss::<G, (Const<1>,), (Const<{G::TOTAL_MOVES}>,)>(
-            v,
-            p,
-            dev.tensor([example.winner]),
-            example.to_true_probs(dev),
-        );
-        let loss_value = loss.array();
-        if i % 5 == 0 {
-            println!("batch {i} | loss = {loss_value:?}");
-        }
-        grads = loss.try_backward()?;
-        if i % batch_accum == 0 {
-            opt.update(model, &grads).unwrap();
-            model.try_zero_grads(&mut grads)?;
-        }
+    for (i, batch) in examples.chunks(batch_accum).enumerate() {
+        let loss_value = update_on_batch(model, batch.to_vec(), opt, dev)?;
+        println!("batch {i} | loss = {loss_value:?}");
     }
     Ok(())
 }
 
 fn examples_to_batch<G: Game + 'static>(
     examples: &Vec<&TrainingExample<G>>,
+    dev: &AutoDevice,
 ) -> Tensor<(usize, Const<{ G::CHANNELS }>, G::BoardSizeX, G::BoardSizeY), f32, AutoDevice>
 where
     [(); G::CHANNELS]: Sized,
 {
-    let mut slices = vec![];
-
-    for example in examples.iter() {
-        slices.push(example.position.clone());
-        println!("{:?}", example.position.shape().strides());
-    }
-
-    slices.stack()
+    let mut encoder: NnEncoder<G, AutoDevice> = NnEncoder::new(dev);
+    encoder.batch_from_encoded(examples.iter().map(|example| &example.position[..]))
 }
 
 fn true_winners_and_probs<G: Game>(
@@ -205,7 +208,7 @@ pub fn update_on_batch<
 
     let (winners, probs) = true_winners_and_probs(&examples, &dev);
 
-    let input = examples_to_batch(&examples).traced(grads);
+    let input = examples_to_batch(&examples, dev).traced(grads);
     let (p, v) = model.try_forward_mut(input)?;
 
     let loss: Tensor<(), f32, AutoDevice, OwnedTape<f32, AutoDevice>> =
@@ -219,3 +222,25 @@ pub fn update_on_batch<
 
     Ok(loss_value)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::UnfinishedTrainingExample;
+    use rust_games_games::TicTacToe;
+    use rust_games_shared::{Game, PlayerId};
+
+    #[test]
+    fn canonical_targets_are_for_the_mover() {
+        let position = vec![0.0; TicTacToe::nn_input_len()];
+        let example =
+            |to_move| UnfinishedTrainingExample::<TicTacToe>::new_canonical(position.clone(), vec![], to_move);
+
+        // First won, which is a loss for Second when it was to move
+        assert_eq!(example(PlayerId::First).finish(1.0).winner, 1.0);
+        assert_eq!(example(PlayerId::Second).finish(1.0).winner, -1.0);
+        assert_eq!(
+            UnfinishedTrainingExample::<TicTacToe>::new(position.clone(), vec![]).finish(1.0).winner,
+            1.0
+        );
+    }
+}
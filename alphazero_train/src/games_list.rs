@@ -40,22 +40,24 @@ where
         mcts_cfg: &MCTSConfig,
     ) where
         [(); G::TOTAL_MOVES]: Sized,
-        [(); <G::BoardSize as ConstDim>::SIZE]: Sized,
+        [(); <G::BoardSizeX as ConstDim>::SIZE]: Sized,
+        [(); <G::BoardSizeY as ConstDim>::SIZE]: Sized,
         [(); <G::TotalBoardSize as ConstDim>::SIZE]: Sized,
         [(); 2 * <G::TotalBoardSize as ConstDim>::SIZE]: Sized,
         <B as BuildOnDevice<AutoDevice, f32>>::Built: Module<
             Tensor<
                 (
+                    usize,
                     Const<{ G::CHANNELS }>,
-                    <G as Game>::BoardSize,
-                    <G as Game>::BoardSize,
+                    <G as Game>::BoardSizeX,
+                    <G as Game>::BoardSizeY,
                 ),
                 f32,
                 AutoDevice,
             >,
             Output = (
-                Tensor<(Const<{ G::TOTAL_MOVES }>,), f32, AutoDevice>,
-                Tensor<(Const<1>,), f32, AutoDevice>,
+                Tensor<(usize, Const<{ G::TOTAL_MOVES }>), f32, AutoDevice>,
+                Tensor<(usize, Const<1>), f32, AutoDevice>,
             ),
             Error = <AutoDevice as HasErr>::Err,
         >,
@@ -68,10 +70,8 @@ where
 
 #[test]
 fn test_cap() {
-    use dfdx::tensor::TensorFrom;
     use rust_games_games::Othello;
-    let dev: AutoDevice = Default::default();
-    let ex = TrainingExample::<Othello>::new(dev.tensor([[[0.0; 8]; 8]; 3]), 0.0, vec![]);
+    let ex = TrainingExample::<Othello>::new(vec![0.0; Othello::nn_input_len()], 0.0, vec![]);
 
     let mut gh = GamesHolder::<Othello> {
         games: vec![ex.clone(), ex.clone()],
@@ -24,6 +24,7 @@ where
     <B as BuildOnDevice<AutoDevice, f32>>::Built: Module<
         Tensor<
             (
+                usize,
                 Const<{ G::CHANNELS }>,
                 <G as Game>::BoardSizeX,
                 <G as Game>::BoardSizeY,
@@ -32,8 +33,8 @@ where
             AutoDevice,
         >,
         Output = (
-            Tensor<(Const<{ G::TOTAL_MOVES }>,), f32, AutoDevice>,
-            Tensor<(Const<1>,), f32, AutoDevice>,
+            Tensor<(usize, Const<{ G::TOTAL_MOVES }>), f32, AutoDevice>,
+            Tensor<(usize, Const<1>), f32, AutoDevice>,
         ),
         Error = <AutoDevice as HasErr>::Err,
     >,
@@ -47,11 +48,13 @@ where
     let dev: AutoDevice = Default::default();
 
     let az1: AlphaZeroPlayer<G, _> =
-        AlphaZeroPlayer::new_from_file::<B>(model_name, data_dir, mcts_cfg.temperature, &dev, true, mcts_cfg.traversal_iter);
+        AlphaZeroPlayer::new_from_file::<B>(model_name, data_dir, mcts_cfg.temperature, &dev, true, mcts_cfg.traversal_iter)
+            .with_canonical(mcts_cfg.canonical);
     let mut player1 = Strategy::new("Player1".to_string(), az1);
 
     let az2: AlphaZeroPlayer<G, _> =
-        AlphaZeroPlayer::new_from_file::<B>(model_name, data_dir, mcts_cfg.temperature, &dev, true, mcts_cfg.traversal_iter);
+        AlphaZeroPlayer::new_from_file::<B>(model_name, data_dir, mcts_cfg.temperature, &dev, true, mcts_cfg.traversal_iter)
+            .with_canonical(mcts_cfg.canonical);
     let mut player2 = Strategy::new("Player2".to_string(), az2);
 
     let mut players_mut = vec![&mut player1, &mut player2];
@@ -197,7 +200,7 @@ mod tests {
             "test",
             "/Applications/Python 3.4/MyScripts/rust_games/data",
             1,
-            &MCTSConfig { temperature: 1.0, traversal_iter: 100}
+            &MCTSConfig { temperature: 1.0, traversal_iter: 100, canonical: false }
         );
     }
 }
@@ -15,7 +15,7 @@ use games_list::GamesHolder;
 use indicatif::{ProgressBar, ProgressStyle};
 use rust_games_games::Othello;
 use rust_games_main::Leaderboard;
-use rust_games_players::Corners;
+use rust_games_players::{AlphaBeta, Corners, EvalGreedy, OthelloEval, SearchBudget};
 use rust_games_shared::Strategy;
 use test_new::test_new_model;
 use train_utils::update_from_gamesholder;
@@ -54,6 +54,10 @@ struct TrainArgs {
 
     #[arg(short, long)]
     data_dir: String,
+
+    // Train on positions seen from the player to move, see Game::to_canonical_nn_input
+    #[arg(long)]
+    canonical: bool,
 }
 
 fn main() {
@@ -71,10 +75,12 @@ fn main() {
     let training_games_cfg = MCTSConfig {
         traversal_iter: args.training_traversal_iter, // AlphaGo: 1600
         temperature: args.training_temp, // AlphaGo: 1.0, but lowers over time
+        canonical: args.canonical,
     };
     let test_games_cfg = MCTSConfig {
         traversal_iter: args.test_traversal_iter, // AlphaGo: 1600
         temperature: args.test_temp,    // AlphaGo: 1.0, but lowers over time
+        canonical: args.canonical,
     };
     ////// Full train loop
 
@@ -154,15 +160,24 @@ fn main() {
         //Print the winner of this iteration
         println!("{:?}", res);
 
-        //// Play some games against Corners
+        //// Play some games against the benchmark players
         let bot = Strategy::new(
             "AlphaZero Best".to_string(),
-            AlphaZeroPlayer::new_from_file::<BoardGameModel<G>>("best",data_dir, 0.2, &dev, false, 100),
+            AlphaZeroPlayer::new_from_file::<BoardGameModel<G>>("best",data_dir, 0.2, &dev, false, 100)
+                .with_canonical(args.canonical),
         );
 
         let corner_player = Strategy::new("Corners".to_string(), Corners::new());
+        let eval_player = Strategy::new(
+            "OthelloEval".to_string(),
+            EvalGreedy::new(OthelloEval::default()),
+        );
+        let search_player = Strategy::new(
+            "OthelloEval Depth 3".to_string(),
+            AlphaBeta::new(OthelloEval::default(), SearchBudget::Depth(3)),
+        );
 
-        let players = vec![bot, corner_player];
+        let players = vec![bot, corner_player, eval_player, search_player];
 
         let mut arena = Leaderboard::new(players);
         arena.play_random_games(args.num_test_games);
@@ -26,6 +26,7 @@ where
     <B as BuildOnDevice<AutoDevice, f32>>::Built: Module<
         Tensor<
             (
+                usize,
                 Const<{ G::CHANNELS }>,
                 <G as Game>::BoardSizeX,
                 <G as Game>::BoardSizeY,
@@ -34,8 +35,8 @@ where
             AutoDevice,
         >,
         Output = (
-            Tensor<(Const<{ G::TOTAL_MOVES }>,), f32, AutoDevice>,
-            Tensor<(Const<1>,), f32, AutoDevice>,
+            Tensor<(usize, Const<{ G::TOTAL_MOVES }>), f32, AutoDevice>,
+            Tensor<(usize, Const<1>), f32, AutoDevice>,
         ),
         Error = <AutoDevice as HasErr>::Err,
     > + Clone,
@@ -50,12 +51,14 @@ where
             mcts_cfg.temperature, 
             &dev, 
             false, 
-            mcts_cfg.traversal_iter),
+            mcts_cfg.traversal_iter)
+            .with_canonical(mcts_cfg.canonical),
     );
 
     let new_az = Strategy::new(
         "New AlphaZero".to_string(),
-        AlphaZeroPlayer::new(new_model.clone(), mcts_cfg.temperature, false, mcts_cfg.traversal_iter),
+        AlphaZeroPlayer::new(new_model.clone(), mcts_cfg.temperature, false, mcts_cfg.traversal_iter)
+            .with_canonical(mcts_cfg.canonical),
     );
 
     let players = vec![old_az, new_az];
@@ -0,0 +1,168 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+/*
+Runs one of our players as a subprocess that GUIs and scripts drive over stdin/stdout, one command per line:
+
+    setposition m1 m2 ...   start from the initial position and play the given moves, if any
+    play m                  play a move
+    genmove                 let the player pick a move and play it, answered by "move m"
+    analyze                 answered by "analysis m1 share1 m2 share2 ... value v", where each share is the
+                            part of the search spent on that move and v is the value for the first player.
+                            Only the alphazero player can analyze
+    quit                    exit
+
+Moves are written as in rust_games_players::move_notation, i.e. as displayed with '_' for spaces.
+Other commands answer "ok" when they work, and anything that fails answers "error" followed by the reason.
+Only the games with players to pick by name can be served: tictactoe, connect4 and othello (see NamedPlayers).
+ */
+use clap::{Parser, ValueEnum};
+use rust_games_games::{Connect4, Othello, TicTacToe};
+use rust_games_players::{move_notation, NamedPlayers, PlayerOptions};
+use rust_games_shared::{Game, Player};
+
+use std::io::BufRead;
+
+// The games implementing NamedPlayers
+#[derive(Clone, Copy, ValueEnum)]
+enum GameName {
+    #[value(name = "tictactoe")]
+    TicTacToe,
+    Connect4,
+    Othello,
+}
+
+#[derive(Parser)]
+struct ServerArgs {
+    #[arg(short, long, value_enum)]
+    game: GameName,
+
+    // random, greedy, alphabeta, uct, alphazero, or one written for the game: solver for tictactoe,
+    // solver or threats for connect4, eval or solver for othello
+    #[arg(short, long, default_value = "alphabeta")]
+    player: String,
+
+    // Search depth for alphabeta
+    #[arg(long, default_value_t = 4)]
+    depth: usize,
+
+    // Iterations for uct and alphazero
+    #[arg(long, default_value_t = 200)]
+    iterations: usize,
+
+    // Positions the connect4 solver may visit per move
+    #[arg(long, default_value_t = 1_000_000)]
+    nodes: usize,
+
+    // Where to load the alphazero model from
+    #[arg(short, long)]
+    data_dir: Option<String>,
+
+    #[arg(short, long, default_value = "best")]
+    model: String,
+
+    #[arg(short, long, default_value_t = 0.2)]
+    temperature: f32,
+
+    #[arg(long)]
+    canonical: bool,
+}
+
+impl ServerArgs {
+    fn player_options(&self) -> PlayerOptions {
+        PlayerOptions {
+            depth: self.depth,
+            iterations: self.iterations,
+            nodes: self.nodes,
+            data_dir: self.data_dir.clone(),
+            model: self.model.clone(),
+            temperature: self.temperature,
+            canonical: self.canonical,
+        }
+    }
+}
+
+fn main() {
+    let args = ServerArgs::parse();
+    match args.game {
+        GameName::TicTacToe => serve::<TicTacToe>(&args),
+        GameName::Connect4 => serve::<Connect4>(&args),
+        GameName::Othello => serve::<Othello>(&args),
+    }
+}
+
+fn parse_move<G: Game>(game: &G, notation: &str) -> Result<G::Move, String> {
+    game.legal_moves()
+        .into_iter()
+        .find(|mv| move_notation(mv) == notation)
+        .ok_or_else(|| format!("{} isn't a legal move", notation))
+}
+
+fn handle<G: NamedPlayers>(
+    line: &str,
+    game: &mut G,
+    player: &dyn Player<G>,
+) -> Result<String, String> {
+    let mut words = line.split_whitespace();
+    match words.next() {
+        Some("setposition") => {
+            let mut position = G::new();
+            for notation in words {
+                let mv = parse_move(&position, notation)?;
+                position.make_move(mv);
+            }
+            *game = position;
+            Ok("ok".to_string())
+        }
+        Some("play") => {
+            let notation = words.next().ok_or("play needs a move")?;
+            let mv = parse_move(game, notation)?;
+            game.make_move(mv);
+            Ok("ok".to_string())
+        }
+        Some("genmove") => {
+            if game.is_over() {
+                return Err("The game is over".to_string());
+            }
+            // Nature makes the moves at chance nodes
+            let mv = if game.is_chance_node() {
+                game.sample_chance()
+            } else {
+                player
+                    .choose_move(game)
+                    .map_err(|err| format!("{:?}", err))?
+            };
+            game.make_move(mv);
+            Ok(format!("move {}", move_notation(&mv)))
+        }
+        Some("analyze") => {
+            if game.is_over() {
+                return Err("The game is over".to_string());
+            }
+            let (shares, value) = G::analysis(player, game).ok_or("This player can't analyze")?;
+            let moves: Vec<String> = shares
+                .iter()
+                .map(|(mv, share)| format!("{} {:.4}", move_notation(mv), share))
+                .collect();
+            Ok(format!("analysis {} value {:.4}", moves.join(" "), value))
+        }
+        Some(other) => Err(format!("Unknown command {}", other)),
+        None => Err("Empty command".to_string()),
+    }
+}
+
+fn serve<G: NamedPlayers>(args: &ServerArgs) {
+    let player = G::named_player(&args.player, &args.player_options())
+        .unwrap_or_else(|reason| panic!("{}", reason));
+    let mut game = G::new();
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        if line.trim() == "quit" {
+            break;
+        }
+        match handle(&line, &mut game, player.as_ref()) {
+            Ok(reply) => println!("{}", reply),
+            Err(reason) => println!("error {}", reason),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+struct Server {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Server {
+    fn start(args: &[&str]) -> Server {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_engine_server"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        Server {
+            stdin: child.stdin.take().unwrap(),
+            stdout: BufReader::new(child.stdout.take().unwrap()),
+            child,
+        }
+    }
+
+    fn ask(&mut self, command: &str) -> String {
+        writeln!(self.stdin, "{}", command).unwrap();
+        let mut reply = String::new();
+        self.stdout.read_line(&mut reply).unwrap();
+        reply.trim().to_string()
+    }
+}
+
+#[test]
+fn answers_each_command() {
+    let mut server = Server::start(&["--game", "tictactoe", "--player", "solver"]);
+
+    assert_eq!(server.ask("setposition 1_1 2_2"), "ok");
+    assert_eq!(server.ask("play 1_2"), "ok");
+    // The solver has to block the top row
+    assert_eq!(server.ask("genmove"), "move 1_3");
+
+    assert_eq!(server.ask("play 1_1"), "error 1_1 isn't a legal move");
+    assert_eq!(server.ask("analyze"), "error This player can't analyze");
+    assert_eq!(server.ask("resign"), "error Unknown command resign");
+
+    writeln!(server.stdin, "quit").unwrap();
+    assert!(server.child.wait().unwrap().success());
+}
+
+#[test]
+fn serves_the_game_specific_players() {
+    for (game, player) in [
+        ("connect4", "solver"),
+        ("connect4", "threats"),
+        ("othello", "eval"),
+    ] {
+        let mut server = Server::start(&["--game", game, "--player", player, "--depth", "2"]);
+        assert!(server.ask("genmove").starts_with("move "));
+        writeln!(server.stdin, "quit").unwrap();
+        assert!(server.child.wait().unwrap().success());
+    }
+}
+
+#[test]
+fn unknown_games_are_usage_errors() {
+    let output = Command::new(env!("CARGO_BIN_EXE_engine_server"))
+        .args(["--game", "chess"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let message = String::from_utf8_lossy(&output.stderr);
+    assert!(message.contains("tictactoe, connect4, othello"), "{}", message);
+}
@@ -0,0 +1,441 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use dfdx::prelude::*;
+use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AmazonsTile {
+    Empty,
+    Amazon(PlayerId),
+    Arrow,
+}
+
+impl fmt::Display for AmazonsTile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let icon = match self {
+            AmazonsTile::Empty => "·",
+            AmazonsTile::Amazon(PlayerId::First) => "W",
+            AmazonsTile::Amazon(PlayerId::Second) => "B",
+            AmazonsTile::Arrow => "x",
+        };
+
+        write!(f, "{}", icon)
+    }
+}
+
+fn square_name(x: isize, y: isize) -> String {
+    if x < 0 || y < 0 || x > 25 {
+        return "??".to_string();
+    }
+    format!("{}{}", (b'a' + x as u8) as char, y + 1)
+}
+
+/*
+A turn in Amazons is a queen move followed by an arrow shot from the amazon that just moved.
+The two halves are played as separate moves by the same player, which keeps the action space
+at N²·8·(N-1) queen moves plus N² arrow targets instead of their product.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AmazonsMove {
+    Queen {
+        x: usize,
+        y: usize,
+        direction: usize,
+        distance: usize,
+    },
+    Arrow(usize, usize),
+}
+
+impl AmazonsMove {
+    fn queen_target(x: usize, y: usize, direction: usize, distance: usize) -> (isize, isize) {
+        let (dx, dy) = DIRECTIONS[direction];
+        (
+            x as isize + dx * distance as isize,
+            y as isize + dy * distance as isize,
+        )
+    }
+}
+
+impl fmt::Display for AmazonsMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            AmazonsMove::Queen {
+                x,
+                y,
+                direction,
+                distance,
+            } => {
+                let (tx, ty) = AmazonsMove::queen_target(x, y, direction, distance);
+                write!(
+                    f,
+                    "{}-{}",
+                    square_name(x as isize, y as isize),
+                    square_name(tx, ty)
+                )
+            }
+            AmazonsMove::Arrow(x, y) => write!(f, "arrow {}", square_name(x as isize, y as isize)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum AmazonsPhase {
+    Move,
+    // The amazon at this square must now shoot
+    Shoot(usize, usize),
+}
+
+// The whole state but the player to move, since the shooting amazon matters as much as the tiles
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct AmazonsBoard<const N: usize> {
+    pub tiles: [[AmazonsTile; N]; N],
+    // The amazon that moved and still has to shoot, if any
+    pub shooter: Option<(usize, usize)>,
+}
+
+/*
+The Game of the Amazons on an N×N board, 10×10 by default and 6×6 for quicker experiments.
+The player who cannot move an amazon at the start of their turn loses.
+ */
+#[derive(Debug, Clone)]
+pub struct Amazons<const N: usize = 10> {
+    board: [[AmazonsTile; N]; N],
+    playing: PlayerId,
+    phase: AmazonsPhase,
+}
+
+impl<const N: usize> Amazons<N> {
+    fn in_bounds(x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && x < N as isize && y < N as isize
+    }
+
+    // Every empty square reachable in a straight unobstructed line from (x, y)
+    fn reachable(&self, x: usize, y: usize) -> Vec<(usize, usize, usize)> {
+        let mut squares = vec![];
+        for (direction, (dx, dy)) in DIRECTIONS.iter().enumerate() {
+            let (mut tx, mut ty) = (x as isize + dx, y as isize + dy);
+            while Self::in_bounds(tx, ty) && self.board[ty as usize][tx as usize] == AmazonsTile::Empty
+            {
+                squares.push((direction, tx as usize, ty as usize));
+                tx += dx;
+                ty += dy;
+            }
+        }
+        squares
+    }
+
+    fn queen_moves(&self) -> Vec<AmazonsMove> {
+        let mut moves = vec![];
+        for y in 0..N {
+            for x in 0..N {
+                if self.board[y][x] != AmazonsTile::Amazon(self.playing) {
+                    continue;
+                }
+                for (direction, tx, ty) in self.reachable(x, y) {
+                    moves.push(AmazonsMove::Queen {
+                        x,
+                        y,
+                        direction,
+                        distance: tx.abs_diff(x).max(ty.abs_diff(y)),
+                    });
+                }
+            }
+        }
+        moves
+    }
+
+    fn start() -> Self {
+        let mut board = [[AmazonsTile::Empty; N]; N];
+        let k = (N - 1) / 3;
+        for (x, y) in [(0, k), (k, 0), (N - 1 - k, 0), (N - 1, k)] {
+            board[y][x] = AmazonsTile::Amazon(PlayerId::First);
+            board[N - 1 - y][x] = AmazonsTile::Amazon(PlayerId::Second);
+        }
+
+        Self {
+            board,
+            playing: PlayerId::First,
+            phase: AmazonsPhase::Move,
+        }
+    }
+
+    fn print_board(&self) {
+        for y in (0..N).rev() {
+            print!("{:>2}", y + 1);
+            for tile in self.board[y].iter() {
+                print!(" {}", tile);
+            }
+            println!()
+        }
+        print!("  ");
+        for x in 0..N {
+            print!(" {}", (b'a' + x as u8) as char);
+        }
+        println!();
+        match self.phase {
+            AmazonsPhase::Move => println!("Currently playing: {:?}", self.playing),
+            AmazonsPhase::Shoot(x, y) => println!(
+                "Currently playing: {:?} (shooting from {})",
+                self.playing,
+                square_name(x as isize, y as isize)
+            ),
+        }
+    }
+
+    fn encode(&self, out: &mut [f32]) {
+        let mut data = Vec::with_capacity(5 * N * N);
+
+        for wanted in [
+            AmazonsTile::Amazon(PlayerId::First),
+            AmazonsTile::Amazon(PlayerId::Second),
+            AmazonsTile::Arrow,
+        ] {
+            for row in self.board.iter() {
+                for tile in row.iter() {
+                    data.push(if *tile == wanted { 1.0 } else { 0.0 });
+                }
+            }
+        }
+
+        // Marks the amazon that still has to shoot, if any
+        for y in 0..N {
+            for x in 0..N {
+                data.push(if self.phase == AmazonsPhase::Shoot(x, y) { 1.0 } else { 0.0 });
+            }
+        }
+
+        let player_num = match self.playing {
+            PlayerId::First => 1.0_f32,
+            PlayerId::Second => 0.0,
+        };
+        data.extend(std::iter::repeat(player_num).take(N * N));
+
+        out.copy_from_slice(&data);
+    }
+
+    // Seen from the given player, whose amazons take the first channel
+    fn encode_perspective(&self, player: PlayerId, out: &mut [f32]) {
+        self.encode(out);
+        if player == PlayerId::Second {
+            let (first_channel, rest) = out.split_at_mut(N * N);
+            first_channel.swap_with_slice(&mut rest[..N * N]);
+        }
+    }
+
+    fn board_state(&self) -> AmazonsBoard<N> {
+        AmazonsBoard {
+            tiles: self.board,
+            shooter: match self.phase {
+                AmazonsPhase::Move => None,
+                AmazonsPhase::Shoot(x, y) => Some((x, y)),
+            },
+        }
+    }
+
+    fn moves(&self) -> Vec<AmazonsMove> {
+        match self.phase {
+            AmazonsPhase::Move => self.queen_moves(),
+            AmazonsPhase::Shoot(x, y) => self
+                .reachable(x, y)
+                .into_iter()
+                .map(|(_, tx, ty)| AmazonsMove::Arrow(tx, ty))
+                .collect(),
+        }
+    }
+
+    fn play(&mut self, m: AmazonsMove) {
+        match m {
+            AmazonsMove::Queen {
+                x,
+                y,
+                direction,
+                distance,
+            } => {
+                let (tx, ty) = AmazonsMove::queen_target(x, y, direction, distance);
+                let (tx, ty) = (tx as usize, ty as usize);
+                self.board[y][x] = AmazonsTile::Empty;
+                self.board[ty][tx] = AmazonsTile::Amazon(self.playing);
+                // The same player now shoots
+                self.phase = AmazonsPhase::Shoot(tx, ty);
+            }
+            AmazonsMove::Arrow(x, y) => {
+                self.board[y][x] = AmazonsTile::Arrow;
+                self.phase = AmazonsPhase::Move;
+                self.playing = self.playing.opponent();
+            }
+        }
+    }
+
+    fn stuck(&self) -> bool {
+        // A moved amazon can always shoot back where it came from, so only the move phase can get stuck
+        self.phase == AmazonsPhase::Move && self.queen_moves().is_empty()
+    }
+
+    fn move_at(i: usize) -> AmazonsMove {
+        let queen_moves = N * N * 8 * (N - 1);
+        if i < queen_moves {
+            let distance = i % (N - 1) + 1;
+            let direction = (i / (N - 1)) % 8;
+            let square = i / (8 * (N - 1));
+            AmazonsMove::Queen {
+                x: square % N,
+                y: square / N,
+                direction,
+                distance,
+            }
+        } else {
+            let square = i - queen_moves;
+            AmazonsMove::Arrow(square % N, square / N)
+        }
+    }
+}
+
+// The trait's sizes can't be computed from a const generic on this toolchain, so each board size gets its own impl,
+// with the area as a literal since the network's shapes only match sizes that are plain numbers
+macro_rules! impl_amazons {
+    ($n:literal, area $area:literal) => {
+        impl Game for Amazons<$n> {
+            type Move = AmazonsMove;
+
+            type Board = AmazonsBoard<$n>;
+
+            type BoardSizeX = Const<$n>;
+            type BoardSizeY = Const<$n>;
+
+            type TotalBoardSize = Const<$area>;
+
+            const CHANNELS: usize = 5;
+
+            const NUM_PLAYERS: usize = 2;
+
+            const TOTAL_MOVES: usize = $n * $n * 8 * ($n - 1) /* Queen moves */ + $n * $n /* Arrows */;
+
+            fn new() -> Self {
+                Self::start()
+            }
+
+            fn print(&self) {
+                self.print_board()
+            }
+
+            fn write_nn_input(&self, out: &mut [f32]) {
+                self.encode(out)
+            }
+
+            fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+                self.encode_perspective(player, out)
+            }
+
+            fn get_board(&self) -> Self::Board {
+                self.board_state()
+            }
+
+            fn legal_moves(&self) -> Vec<Self::Move> {
+                self.moves()
+            }
+
+            fn make_move(&mut self, m: Self::Move) {
+                self.play(m)
+            }
+
+            fn is_over(&self) -> bool {
+                self.stuck()
+            }
+
+            fn get_result(&self) -> Option<GameResult> {
+                if !self.is_over() {
+                    return None;
+                }
+
+                Some(GameResult::Winner(self.playing.opponent()))
+            }
+
+            fn current_player(&self) -> PlayerId {
+                self.playing
+            }
+
+            fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES] {
+                core::array::from_fn(Self::move_at)
+            }
+
+            fn associate_players(
+                players: Vec<&Strategy<Self>>,
+            ) -> std::collections::HashMap<PlayerId, &Strategy<Self>> {
+                let mut players_map = HashMap::new();
+                players_map.insert(PlayerId::First, players[0]);
+                players_map.insert(PlayerId::Second, players[1]);
+
+                players_map
+            }
+        }
+    };
+}
+
+impl_amazons!(6, area 36);
+impl_amazons!(10, area 100);
+
+#[cfg(test)]
+mod test {
+    use super::{Amazons, AmazonsMove};
+    use rust_games_shared::{Game, PlayerId};
+    use std::collections::HashSet;
+
+    #[test]
+    fn legal_moves_are_possible_moves() {
+        let g: Amazons<6> = Amazons::new();
+        let all: HashSet<AmazonsMove> = Amazons::<6>::all_possible_moves().into_iter().collect();
+        assert_eq!(all.len(), Amazons::<6>::TOTAL_MOVES);
+        assert!(g.legal_moves().iter().all(|m| all.contains(m)));
+    }
+
+    #[test]
+    fn shooting_keeps_then_passes_the_turn() {
+        let mut g: Amazons<6> = Amazons::new();
+        let m = g.legal_moves()[0];
+        g.make_move(m);
+        assert_eq!(g.current_player(), PlayerId::First);
+        // The board tells the shooting amazon apart from the same tiles before a move
+        assert!(g.get_board().shooter.is_some());
+        assert!(g
+            .legal_moves()
+            .iter()
+            .all(|m| matches!(m, AmazonsMove::Arrow(_, _))));
+
+        let arrow = g.legal_moves()[0];
+        g.make_move(arrow);
+        assert_eq!(g.current_player(), PlayerId::Second);
+        assert!(g.get_board().shooter.is_none());
+
+        // The second player sees their own amazons first
+        let plane = 6 * 6;
+        let mut absolute = vec![0.0; Amazons::<6>::nn_input_len()];
+        let mut canonical = vec![0.0; Amazons::<6>::nn_input_len()];
+        g.write_nn_input(&mut absolute);
+        g.write_canonical_nn_input(&mut canonical);
+        assert_eq!(canonical[..plane], absolute[plane..2 * plane]);
+        assert_eq!(canonical[plane..2 * plane], absolute[..plane]);
+        assert_eq!(canonical[2 * plane..], absolute[2 * plane..]);
+    }
+
+    #[test]
+    fn games_finish() {
+        let mut g: Amazons<6> = Amazons::new();
+        while !g.is_over() {
+            let m = *g.legal_moves().last().unwrap();
+            g.make_move(m);
+        }
+        assert!(g.get_result().is_some());
+    }
+}
@@ -0,0 +1,519 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use dfdx::prelude::*;
+use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
+
+// The `from` of a step entering from the bar
+const BAR: usize = 24;
+const CHECKERS: u8 = 15;
+
+/*
+Points are numbered 0..24 from the first player's home board outwards.
+The first player moves its checkers towards point 0, the second player towards point 23.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackgammonMove {
+    // Chance move: the dice come up (low, high)
+    Roll(u8, u8),
+    // Move one checker from a point (or BAR) by the pips on one die
+    Step { from: usize, die: u8 },
+    // Forfeit the rest of the roll when no die can be used
+    Pass,
+}
+
+impl fmt::Display for BackgammonMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackgammonMove::Roll(a, b) => write!(f, "Roll {}-{}", a, b),
+            BackgammonMove::Step { from, die } if *from == BAR => write!(f, "bar/{}", die),
+            BackgammonMove::Step { from, die } => write!(f, "{}/{}", from + 1, die),
+            BackgammonMove::Pass => write!(f, "Pass"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BackgammonBoard {
+    // Positive counts are the first player's checkers, negative the second's
+    pub points: [i8; 24],
+    pub bar: [u8; 2],
+    pub off: [u8; 2],
+    // Unused dice of the current roll, 0 marks a used die
+    pub dice: [u8; 4],
+}
+
+/*
+Standard backgammon without the doubling cube.
+A turn is a chance move rolling the dice followed by one Step per usable die,
+so the same player keeps moving until the roll is used up. Players must use as many dice as possible,
+and the larger die when only one of two can be played.
+Gammons and backgammons are reported through `score_margin`.
+ */
+#[derive(Debug, Clone)]
+pub struct Backgammon {
+    board: BackgammonBoard,
+    playing: PlayerId,
+}
+
+fn sign(player: PlayerId) -> i8 {
+    match player {
+        PlayerId::First => 1,
+        PlayerId::Second => -1,
+    }
+}
+
+impl Backgammon {
+    fn checkers_on(&self, point: usize, player: PlayerId) -> u8 {
+        let count = self.board.points[point] * sign(player);
+        if count > 0 {
+            count as u8
+        } else {
+            0
+        }
+    }
+
+    fn home_board(player: PlayerId) -> std::ops::Range<usize> {
+        match player {
+            PlayerId::First => 0..6,
+            PlayerId::Second => 18..24,
+        }
+    }
+
+    fn remaining_dice(&self) -> Vec<u8> {
+        self.board.dice.iter().copied().filter(|d| *d != 0).collect()
+    }
+
+    fn all_home(&self, player: PlayerId) -> bool {
+        let home = Self::home_board(player);
+        self.board.bar[usize::from(player)] == 0
+            && (0..24).all(|point| home.contains(&point) || self.checkers_on(point, player) == 0)
+    }
+
+    // Where a checker lands, negative or past 23 meaning borne off
+    fn step_target(player: PlayerId, from: usize, die: u8) -> isize {
+        let die = die as isize;
+        match (player, from) {
+            (PlayerId::First, BAR) => 24 - die,
+            (PlayerId::Second, BAR) => die - 1,
+            (PlayerId::First, _) => from as isize - die,
+            (PlayerId::Second, _) => from as isize + die,
+        }
+    }
+
+    fn can_step(&self, from: usize, die: u8) -> bool {
+        let player = self.playing;
+        let on_bar = self.board.bar[usize::from(player)] > 0;
+
+        if on_bar != (from == BAR) {
+            return false;
+        }
+        if from != BAR && self.checkers_on(from, player) == 0 {
+            return false;
+        }
+
+        let target = Self::step_target(player, from, die);
+        if (0..24).contains(&target) {
+            return self.checkers_on(target as usize, player.opponent()) < 2;
+        }
+
+        // Bearing off
+        if !self.all_home(player) {
+            return false;
+        }
+        let exact = target == -1 || target == 24;
+        if exact {
+            return true;
+        }
+        // A higher die may only bear off from the furthest occupied point
+        let further: Vec<usize> = match player {
+            PlayerId::First => (from + 1..6).collect(),
+            PlayerId::Second => (18..from).collect(),
+        };
+        further
+            .into_iter()
+            .all(|point| self.checkers_on(point, player) == 0)
+    }
+
+    fn apply_step(&mut self, from: usize, die: u8) {
+        let player = self.playing;
+        let p = usize::from(player);
+
+        if from == BAR {
+            self.board.bar[p] -= 1;
+        } else {
+            self.board.points[from] -= sign(player);
+        }
+
+        let target = Self::step_target(player, from, die);
+        if (0..24).contains(&target) {
+            let target = target as usize;
+            // Hit a lone opposing checker
+            if self.checkers_on(target, player.opponent()) == 1 {
+                self.board.points[target] = 0;
+                self.board.bar[usize::from(player.opponent())] += 1;
+            }
+            self.board.points[target] += sign(player);
+        } else {
+            self.board.off[p] += 1;
+        }
+
+        let used = self
+            .board
+            .dice
+            .iter()
+            .position(|d| *d == die)
+            .expect("Die was not rolled");
+        self.board.dice[used] = 0;
+    }
+
+    // Every single checker move, ignoring the rules about using as much of the roll as possible
+    fn single_steps(&self) -> Vec<(usize, u8)> {
+        let mut dice = self.remaining_dice();
+        dice.sort();
+        dice.dedup();
+
+        let mut steps = vec![];
+        for die in dice {
+            for from in 0..=BAR {
+                if self.can_step(from, die) {
+                    steps.push((from, die));
+                }
+            }
+        }
+        steps
+    }
+
+    // The most dice that can still be used this turn
+    fn max_playable(&self) -> usize {
+        let remaining = self.remaining_dice().len();
+        let mut best = 0;
+        for (from, die) in self.single_steps() {
+            let mut next = self.clone();
+            next.apply_step(from, die);
+            best = best.max(1 + next.max_playable());
+            if best == remaining {
+                break;
+            }
+        }
+        best
+    }
+
+    pub fn pip_count(&self, player: PlayerId) -> usize {
+        let mut pips = 25 * self.board.bar[usize::from(player)] as usize;
+        for point in 0..24 {
+            let distance = match player {
+                PlayerId::First => point + 1,
+                PlayerId::Second => 24 - point,
+            };
+            pips += distance * self.checkers_on(point, player) as usize;
+        }
+        pips
+    }
+}
+
+impl Game for Backgammon {
+    type Move = BackgammonMove;
+
+    type Board = BackgammonBoard;
+
+    // Points 0..24, the bar, and borne off checkers in a single row
+    type BoardSizeX = Const<1>;
+    type BoardSizeY = Const<26>;
+
+    type TotalBoardSize = Const<26>;
+
+    const CHANNELS: usize = 8 /* Checker counts */ + 6 /* Dice */ + 1 /* Player */;
+
+    const NUM_PLAYERS: usize = 2;
+
+    const TOTAL_MOVES: usize = 21 /* Rolls */ + 25 * 6 /* Steps */ + 1 /* Passing */;
+
+    fn new() -> Self {
+        let mut points = [0_i8; 24];
+        for (point, count) in [(23, 2), (12, 5), (7, 3), (5, 5)] {
+            points[point] = count;
+            points[23 - point] = -count;
+        }
+
+        Self {
+            board: BackgammonBoard {
+                points,
+                bar: [0, 0],
+                off: [0, 0],
+                dice: [0; 4],
+            },
+            playing: PlayerId::First,
+        }
+    }
+
+    fn print(&self) {
+        let cell = |point: usize| match self.board.points[point] {
+            0 => "  .".to_string(),
+            n => format!("{:>+3}", n),
+        };
+        println!(" 13 14 15 16 17 18   19 20 21 22 23 24");
+        for point in 12..24 {
+            if point == 18 {
+                print!("  ");
+            }
+            print!("{}", cell(point));
+        }
+        println!();
+        for point in (0..12).rev() {
+            if point == 5 {
+                print!("  ");
+            }
+            print!("{}", cell(point));
+        }
+        println!();
+        println!(" 12 11 10  9  8  7    6  5  4  3  2  1");
+        println!(
+            "Bar: {} - {}, Off: {} - {}",
+            self.board.bar[0], self.board.bar[1], self.board.off[0], self.board.off[1]
+        );
+        println!("Dice: {:?}", self.remaining_dice());
+        println!("Currently playing: {:?}", self.playing);
+    }
+
+    fn write_nn_input(&self, out: &mut [f32]) {
+        let mut data = Vec::with_capacity(Self::CHANNELS * 26);
+
+        for player in [PlayerId::First, PlayerId::Second] {
+            let mut counts: Vec<f32> = (0..24)
+                .map(|point| self.checkers_on(point, player) as f32)
+                .collect();
+            counts.push(self.board.bar[usize::from(player)] as f32);
+            counts.push(self.board.off[usize::from(player)] as f32);
+
+            // TD-Gammon style: one plane per threshold, then the overflow
+            for threshold in 1..=3 {
+                data.extend(counts.iter().map(|n| if *n >= threshold as f32 { 1.0 } else { 0.0 }));
+            }
+            data.extend(counts.iter().map(|n| (n - 3.0).max(0.0) / 2.0));
+        }
+
+        for face in 1..=6 {
+            let count = self.board.dice.iter().filter(|d| **d == face).count();
+            data.extend(std::iter::repeat(count as f32 / 4.0).take(26));
+        }
+
+        let player_num = match self.playing {
+            PlayerId::First => 1.0_f32,
+            PlayerId::Second => 0.0,
+        };
+        data.extend(std::iter::repeat(player_num).take(26));
+
+        out.copy_from_slice(&data);
+    }
+
+    // The player's four checker planes come first, then the opponent's
+    fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+        self.write_nn_input(out);
+        if player == PlayerId::Second {
+            let (first_planes, rest) = out.split_at_mut(4 * 26);
+            first_planes.swap_with_slice(&mut rest[..4 * 26]);
+        }
+    }
+
+    fn get_board(&self) -> Self::Board {
+        self.board.clone()
+    }
+
+    fn legal_moves(&self) -> Vec<Self::Move> {
+        if self.is_over() {
+            return vec![];
+        }
+        if self.is_chance_node() {
+            return self.chance_outcomes().into_iter().map(|(m, _)| m).collect();
+        }
+
+        let steps = self.single_steps();
+        if steps.is_empty() {
+            return vec![BackgammonMove::Pass];
+        }
+
+        // Only steps that still allow the most dice to be used are legal
+        let max = self.max_playable();
+        let mut steps: Vec<(usize, u8)> = steps
+            .into_iter()
+            .filter(|(from, die)| {
+                let mut next = self.clone();
+                next.apply_step(*from, *die);
+                1 + next.max_playable() == max
+            })
+            .collect();
+
+        // If only one of two different dice can be used, it must be the larger one
+        let dice = self.remaining_dice();
+        if max == 1 && dice.len() == 2 && dice[0] != dice[1] {
+            let higher = dice[0].max(dice[1]);
+            if steps.iter().any(|(_, die)| *die == higher) {
+                steps.retain(|(_, die)| *die == higher);
+            }
+        }
+
+        steps
+            .into_iter()
+            .map(|(from, die)| BackgammonMove::Step { from, die })
+            .collect()
+    }
+
+    fn make_move(&mut self, m: Self::Move) {
+        match m {
+            BackgammonMove::Roll(a, b) => {
+                self.board.dice = if a == b { [a; 4] } else { [a, b, 0, 0] };
+            }
+            BackgammonMove::Step { from, die } => {
+                self.apply_step(from, die);
+                if self.remaining_dice().is_empty() {
+                    self.playing = self.playing.opponent();
+                }
+            }
+            BackgammonMove::Pass => {
+                self.board.dice = [0; 4];
+                self.playing = self.playing.opponent();
+            }
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.board.off.iter().any(|off| *off == CHECKERS)
+    }
+
+    fn get_result(&self) -> Option<GameResult> {
+        if self.board.off[0] == CHECKERS {
+            Some(GameResult::Winner(PlayerId::First))
+        } else if self.board.off[1] == CHECKERS {
+            Some(GameResult::Winner(PlayerId::Second))
+        } else {
+            None
+        }
+    }
+
+    // 1 for a single game, 2 for a gammon and 3 for a backgammon
+    fn score_margin(&self) -> Option<f32> {
+        let winner = match self.get_result()? {
+            GameResult::Winner(winner) => winner,
+            _ => return None,
+        };
+        let loser = winner.opponent();
+
+        let points = if self.board.off[usize::from(loser)] > 0 {
+            1.0
+        } else if self.board.bar[usize::from(loser)] > 0
+            || Self::home_board(winner).any(|point| self.checkers_on(point, loser) > 0)
+        {
+            3.0
+        } else {
+            2.0
+        };
+
+        Some(points * sign(winner) as f32)
+    }
+
+    fn current_player(&self) -> PlayerId {
+        self.playing
+    }
+
+    fn is_chance_node(&self) -> bool {
+        !self.is_over() && self.remaining_dice().is_empty()
+    }
+
+    fn chance_outcomes(&self) -> Vec<(Self::Move, f32)> {
+        let mut outcomes = vec![];
+        for a in 1..=6 {
+            for b in a..=6 {
+                let prob = if a == b { 1.0 / 36.0 } else { 2.0 / 36.0 };
+                outcomes.push((BackgammonMove::Roll(a, b), prob));
+            }
+        }
+        outcomes
+    }
+
+    fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES] {
+        let mut moves = [BackgammonMove::Pass; Self::TOTAL_MOVES];
+
+        let mut i = 0;
+        for a in 1..=6 {
+            for b in a..=6 {
+                moves[i] = BackgammonMove::Roll(a, b);
+                i += 1;
+            }
+        }
+        for from in 0..=BAR {
+            for die in 1..=6 {
+                moves[i] = BackgammonMove::Step { from, die };
+                i += 1;
+            }
+        }
+
+        moves
+    }
+
+    fn associate_players(
+        players: Vec<&Strategy<Self>>,
+    ) -> std::collections::HashMap<PlayerId, &Strategy<Self>> {
+        let mut players_map = HashMap::new();
+        players_map.insert(PlayerId::First, players[0]);
+        players_map.insert(PlayerId::Second, players[1]);
+
+        players_map
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Backgammon, BackgammonMove, BAR};
+    use rust_games_shared::{Game, PlayerId};
+
+    #[test]
+    fn opening_is_a_roll() {
+        let g = Backgammon::new();
+        assert!(g.is_chance_node());
+        let total: f32 = g.chance_outcomes().iter().map(|(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-5);
+        assert_eq!(g.pip_count(PlayerId::First), 167);
+        assert_eq!(g.pip_count(PlayerId::Second), 167);
+    }
+
+    #[test]
+    fn doubles_give_four_steps() {
+        let mut g = Backgammon::new();
+        g.make_move(BackgammonMove::Roll(3, 3));
+        for _ in 0..4 {
+            assert_eq!(g.current_player(), PlayerId::First);
+            let m = g.legal_moves()[0];
+            g.make_move(m);
+        }
+        assert_eq!(g.current_player(), PlayerId::Second);
+        assert!(g.is_chance_node());
+    }
+
+    #[test]
+    fn must_enter_from_bar() {
+        let mut g = Backgammon::new();
+        g.board.points[23] = 1;
+        g.board.bar[0] = 1;
+        g.make_move(BackgammonMove::Roll(1, 2));
+        assert!(g.legal_moves().iter().all(|m| match m {
+            BackgammonMove::Step { from, .. } => *from == BAR,
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn games_finish() {
+        let mut g = Backgammon::new();
+        while !g.is_over() {
+            let m = if g.is_chance_node() {
+                g.sample_chance()
+            } else {
+                g.legal_moves()[0]
+            };
+            g.make_move(m);
+        }
+        assert!(g.get_result().is_some());
+        assert!(g.score_margin().is_some());
+    }
+}
@@ -0,0 +1,502 @@
+use core::fmt;
+use std::{cmp::Ordering, collections::HashMap, sync::OnceLock};
+
+use dfdx::prelude::*;
+use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
+
+const SIZE: usize = 14;
+const NUM_PIECES: usize = 21;
+const NUM_ORIENTATIONS: usize = 91;
+const START_SQUARES: [(usize, usize); 2] = [(4, 4), (9, 9)];
+
+const PIECE_NAMES: [&str; NUM_PIECES] = [
+    "1", "2", "I3", "V3", "I4", "L4", "T4", "O4", "Z4", "I5", "L5", "Y5", "N5", "P5", "U5", "V5",
+    "W5", "Z5", "T5", "F5", "X5",
+];
+
+const PIECES: [&[(usize, usize)]; NUM_PIECES] = [
+    &[(0, 0)],
+    &[(0, 0), (1, 0)],
+    &[(0, 0), (1, 0), (2, 0)],
+    &[(0, 0), (1, 0), (0, 1)],
+    &[(0, 0), (1, 0), (2, 0), (3, 0)],
+    &[(0, 0), (1, 0), (2, 0), (2, 1)],
+    &[(0, 0), (1, 0), (2, 0), (1, 1)],
+    &[(0, 0), (1, 0), (0, 1), (1, 1)],
+    &[(1, 0), (2, 0), (0, 1), (1, 1)],
+    &[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)],
+    &[(0, 0), (1, 0), (2, 0), (3, 0), (3, 1)],
+    &[(0, 0), (1, 0), (2, 0), (3, 0), (1, 1)],
+    &[(0, 0), (1, 0), (2, 0), (2, 1), (3, 1)],
+    &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)],
+    &[(0, 0), (0, 1), (1, 0), (2, 0), (2, 1)],
+    &[(0, 0), (1, 0), (2, 0), (0, 1), (0, 2)],
+    &[(0, 0), (0, 1), (1, 1), (1, 2), (2, 2)],
+    &[(0, 0), (1, 0), (1, 1), (1, 2), (2, 2)],
+    &[(0, 0), (1, 0), (2, 0), (1, 1), (1, 2)],
+    &[(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)],
+    &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+];
+
+// The cells a placed piece covers
+type Shape = Vec<(usize, usize)>;
+
+/*
+Every distinct orientation of every piece, as cells shifted so the smallest x and y are 0.
+Orientations are listed in a fixed order, so (piece, orientation) is a canonical name for a shape.
+ */
+fn orientations() -> &'static Vec<Vec<Shape>> {
+    static ORIENTATIONS: OnceLock<Vec<Vec<Shape>>> = OnceLock::new();
+    ORIENTATIONS.get_or_init(|| {
+        PIECES
+            .iter()
+            .map(|cells| {
+                let mut shapes: Vec<Vec<(usize, usize)>> = vec![];
+                for flip in [false, true] {
+                    for rotation in 0..4 {
+                        let mut shape: Vec<(isize, isize)> = cells
+                            .iter()
+                            .map(|&(x, y)| {
+                                let (mut x, mut y) = (x as isize, y as isize);
+                                if flip {
+                                    x = -x;
+                                }
+                                for _ in 0..rotation {
+                                    (x, y) = (-y, x);
+                                }
+                                (x, y)
+                            })
+                            .collect();
+                        let min_x = shape.iter().map(|c| c.0).min().unwrap();
+                        let min_y = shape.iter().map(|c| c.1).min().unwrap();
+                        for cell in shape.iter_mut() {
+                            *cell = (cell.0 - min_x, cell.1 - min_y);
+                        }
+                        let mut shape: Vec<(usize, usize)> = shape
+                            .into_iter()
+                            .map(|(x, y)| (x as usize, y as usize))
+                            .collect();
+                        shape.sort();
+                        if !shapes.contains(&shape) {
+                            shapes.push(shape);
+                        }
+                    }
+                }
+                shapes
+            })
+            .collect()
+    })
+}
+
+/*
+A placement is a piece, one of its canonical orientations, and the square its bounding box starts on.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlokusMove {
+    Place {
+        piece: u8,
+        orientation: u8,
+        x: u8,
+        y: u8,
+    },
+    Pass,
+}
+
+impl BlokusMove {
+    fn cells(&self) -> Vec<(usize, usize)> {
+        match *self {
+            BlokusMove::Pass => vec![],
+            BlokusMove::Place {
+                piece,
+                orientation,
+                x,
+                y,
+            } => orientations()[piece as usize][orientation as usize]
+                .iter()
+                .map(|&(dx, dy)| (x as usize + dx, y as usize + dy))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for BlokusMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlokusMove::Pass => write!(f, "Pass"),
+            BlokusMove::Place {
+                piece,
+                orientation,
+                x,
+                y,
+            } => write!(
+                f,
+                "{}:{} {}{}",
+                PIECE_NAMES[*piece as usize],
+                orientation,
+                (b'a' + x) as char,
+                y + 1
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlokusBoard {
+    pub cells: [[Option<PlayerId>; SIZE]; SIZE],
+    pub remaining: [[bool; NUM_PIECES]; 2],
+    pub last_piece: [Option<u8>; 2],
+}
+
+/*
+Blokus Duo: two players, a 14×14 board and the 21 polyominoes each.
+A new piece must touch one of its owner's pieces at a corner but never along an edge,
+and each player's first piece must cover their starting square. Players pass when stuck,
+and the game ends when both pass in a row. Scores are minus the unplaced squares,
+with 15 bonus points for placing every piece and 5 more if the monomino went last.
+ */
+#[derive(Debug, Clone)]
+pub struct BlokusDuo {
+    board: BlokusBoard,
+    playing: PlayerId,
+    consecutive_passes: usize,
+}
+
+impl BlokusDuo {
+    fn owned_by(&self, x: isize, y: isize, player: PlayerId) -> bool {
+        x >= 0
+            && y >= 0
+            && x < SIZE as isize
+            && y < SIZE as isize
+            && self.board.cells[y as usize][x as usize] == Some(player)
+    }
+
+    fn is_first_placement(&self, player: PlayerId) -> bool {
+        self.board.remaining[usize::from(player)]
+            .iter()
+            .all(|remaining| *remaining)
+    }
+
+    fn fits(&self, cells: &[(usize, usize)]) -> bool {
+        let player = self.playing;
+        let mut touches_corner = false;
+
+        for &(x, y) in cells {
+            if x >= SIZE || y >= SIZE || self.board.cells[y][x].is_some() {
+                return false;
+            }
+            let (x, y) = (x as isize, y as isize);
+            for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+                if self.owned_by(x + dx, y + dy, player) {
+                    return false;
+                }
+            }
+            for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                if self.owned_by(x + dx, y + dy, player) {
+                    touches_corner = true;
+                }
+            }
+        }
+
+        if self.is_first_placement(player) {
+            cells.contains(&START_SQUARES[usize::from(player)])
+        } else {
+            touches_corner
+        }
+    }
+
+    fn placements(&self) -> Vec<BlokusMove> {
+        let mut moves = vec![];
+        let remaining = &self.board.remaining[usize::from(self.playing)];
+
+        for (piece, shapes) in orientations().iter().enumerate() {
+            if !remaining[piece] {
+                continue;
+            }
+            for (orientation, _) in shapes.iter().enumerate() {
+                for y in 0..SIZE {
+                    for x in 0..SIZE {
+                        let m = BlokusMove::Place {
+                            piece: piece as u8,
+                            orientation: orientation as u8,
+                            x: x as u8,
+                            y: y as u8,
+                        };
+                        if self.fits(&m.cells()) {
+                            moves.push(m);
+                        }
+                    }
+                }
+            }
+        }
+        moves
+    }
+
+    pub fn score(&self, player: PlayerId) -> i32 {
+        let p = usize::from(player);
+        let unplaced: usize = PIECES
+            .iter()
+            .zip(self.board.remaining[p])
+            .filter(|(_, remaining)| *remaining)
+            .map(|(cells, _)| cells.len())
+            .sum();
+
+        if unplaced > 0 {
+            return -(unplaced as i32);
+        }
+        // The monomino is piece 0
+        if self.board.last_piece[p] == Some(0) {
+            20
+        } else {
+            15
+        }
+    }
+}
+
+impl Game for BlokusDuo {
+    type Move = BlokusMove;
+
+    type Board = BlokusBoard;
+
+    type BoardSizeX = Const<14>;
+    type BoardSizeY = Const<14>;
+
+    type TotalBoardSize = Const<196>;
+
+    const CHANNELS: usize = 2 /* Pieces */ + 2 /* Squares left */ + 1 /* Player */;
+
+    const NUM_PLAYERS: usize = 2;
+
+    const TOTAL_MOVES: usize = NUM_ORIENTATIONS * SIZE * SIZE /* Placements */ + 1 /* Passing */;
+
+    fn new() -> Self {
+        Self {
+            board: BlokusBoard {
+                cells: [[None; SIZE]; SIZE],
+                remaining: [[true; NUM_PIECES]; 2],
+                last_piece: [None; 2],
+            },
+            playing: PlayerId::First,
+            consecutive_passes: 0,
+        }
+    }
+
+    fn print(&self) {
+        for y in (0..SIZE).rev() {
+            print!("{:>2}", y + 1);
+            for x in 0..SIZE {
+                let icon = match self.board.cells[y][x] {
+                    Some(PlayerId::First) => "1",
+                    Some(PlayerId::Second) => "2",
+                    None if START_SQUARES.contains(&(x, y)) => "*",
+                    None => "·",
+                };
+                print!(" {}", icon);
+            }
+            println!();
+        }
+        println!("   a b c d e f g h i j k l m n");
+        for player in [PlayerId::First, PlayerId::Second] {
+            let left: Vec<&str> = PIECE_NAMES
+                .iter()
+                .zip(self.board.remaining[usize::from(player)])
+                .filter(|(_, remaining)| *remaining)
+                .map(|(name, _)| *name)
+                .collect();
+            println!("{:?} has {}", player, left.join(" "));
+        }
+        println!("Currently playing: {:?}", self.playing);
+    }
+
+    fn write_nn_input(&self, out: &mut [f32]) {
+        let mut data = Vec::with_capacity(Self::CHANNELS * SIZE * SIZE);
+
+        for player in [PlayerId::First, PlayerId::Second] {
+            for row in self.board.cells.iter() {
+                for cell in row.iter() {
+                    data.push(if *cell == Some(player) { 1.0 } else { 0.0 });
+                }
+            }
+        }
+
+        // 89 squares make up a full set of pieces
+        for player in [PlayerId::First, PlayerId::Second] {
+            let left = self.score(player).min(0).unsigned_abs() as f32 / 89.0;
+            data.extend(std::iter::repeat(left).take(SIZE * SIZE));
+        }
+
+        let player_num = match self.playing {
+            PlayerId::First => 1.0_f32,
+            PlayerId::Second => 0.0,
+        };
+        data.extend(std::iter::repeat(player_num).take(SIZE * SIZE));
+
+        out.copy_from_slice(&data);
+    }
+
+    // The player's pieces and squares left come first, each followed by the opponent's
+    fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+        self.write_nn_input(out);
+        if player == PlayerId::Second {
+            let plane = SIZE * SIZE;
+            for first in [0, 2] {
+                let (first_channel, rest) = out[first * plane..].split_at_mut(plane);
+                first_channel.swap_with_slice(&mut rest[..plane]);
+            }
+        }
+    }
+
+    fn get_board(&self) -> Self::Board {
+        self.board.clone()
+    }
+
+    fn legal_moves(&self) -> Vec<Self::Move> {
+        if self.is_over() {
+            return vec![];
+        }
+
+        let moves = self.placements();
+        if moves.is_empty() {
+            vec![BlokusMove::Pass]
+        } else {
+            moves
+        }
+    }
+
+    fn make_move(&mut self, m: Self::Move) {
+        match m {
+            BlokusMove::Pass => self.consecutive_passes += 1,
+            BlokusMove::Place { piece, .. } => {
+                let p = usize::from(self.playing);
+                for (x, y) in m.cells() {
+                    self.board.cells[y][x] = Some(self.playing);
+                }
+                self.board.remaining[p][piece as usize] = false;
+                self.board.last_piece[p] = Some(piece);
+                self.consecutive_passes = 0;
+            }
+        }
+
+        self.playing = self.playing.opponent();
+    }
+
+    fn is_over(&self) -> bool {
+        self.consecutive_passes >= 2
+    }
+
+    fn get_result(&self) -> Option<GameResult> {
+        if !self.is_over() {
+            return None;
+        }
+
+        let first = self.score(PlayerId::First);
+        let second = self.score(PlayerId::Second);
+        match first.cmp(&second) {
+            Ordering::Greater => Some(GameResult::Winner(PlayerId::First)),
+            Ordering::Equal => Some(GameResult::Tie),
+            Ordering::Less => Some(GameResult::Winner(PlayerId::Second)),
+        }
+    }
+
+    fn score_margin(&self) -> Option<f32> {
+        Some((self.score(PlayerId::First) - self.score(PlayerId::Second)) as f32)
+    }
+
+    fn current_player(&self) -> PlayerId {
+        self.playing
+    }
+
+    fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES] {
+        let mut moves = [BlokusMove::Pass; Self::TOTAL_MOVES];
+
+        let mut i = 0;
+        for (piece, shapes) in orientations().iter().enumerate() {
+            for orientation in 0..shapes.len() {
+                for y in 0..SIZE {
+                    for x in 0..SIZE {
+                        moves[i] = BlokusMove::Place {
+                            piece: piece as u8,
+                            orientation: orientation as u8,
+                            x: x as u8,
+                            y: y as u8,
+                        };
+                        i += 1;
+                    }
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn associate_players(
+        players: Vec<&Strategy<Self>>,
+    ) -> std::collections::HashMap<PlayerId, &Strategy<Self>> {
+        let mut players_map = HashMap::new();
+        players_map.insert(PlayerId::First, players[0]);
+        players_map.insert(PlayerId::Second, players[1]);
+
+        players_map
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{orientations, BlokusDuo, BlokusMove, NUM_ORIENTATIONS, START_SQUARES};
+    use rust_games_shared::{Game, GameResult};
+
+    #[test]
+    fn orientation_count() {
+        let total: usize = orientations().iter().map(|shapes| shapes.len()).sum();
+        assert_eq!(total, NUM_ORIENTATIONS);
+    }
+
+    #[test]
+    fn first_piece_covers_start() {
+        let g = BlokusDuo::new();
+        let moves = g.legal_moves();
+        assert!(!moves.is_empty());
+        assert!(moves
+            .iter()
+            .all(|m| m.cells().contains(&START_SQUARES[0])));
+    }
+
+    #[test]
+    fn pieces_touch_at_corners() {
+        let mut g = BlokusDuo::new();
+        let monomino = |x, y| BlokusMove::Place {
+            piece: 0,
+            orientation: 0,
+            x,
+            y,
+        };
+        g.make_move(monomino(4, 4));
+        g.make_move(monomino(9, 9));
+
+        let domino = |x, y| BlokusMove::Place {
+            piece: 1,
+            orientation: 0,
+            x,
+            y,
+        };
+        let moves = g.legal_moves();
+        assert!(!moves.contains(&domino(5, 4)));
+        assert!(moves.contains(&domino(5, 5)));
+        assert!(moves
+            .iter()
+            .filter(|m| matches!(m, BlokusMove::Place { piece: 1, .. }))
+            .all(|m| m.cells().iter().any(|&(x, y)| x.abs_diff(4) == 1 && y.abs_diff(4) == 1)));
+    }
+
+    #[test]
+    fn games_finish() {
+        let mut g = BlokusDuo::new();
+        while !g.is_over() {
+            let m = g.legal_moves()[0];
+            g.make_move(m);
+        }
+        assert!(matches!(
+            g.get_result(),
+            Some(GameResult::Winner(_)) | Some(GameResult::Tie)
+        ));
+    }
+}
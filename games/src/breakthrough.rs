@@ -0,0 +1,363 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use dfdx::prelude::*;
+use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BreakthroughDirection {
+    Left,
+    Forward,
+    Right,
+}
+
+impl BreakthroughDirection {
+    const ALL: [BreakthroughDirection; 3] = [
+        BreakthroughDirection::Left,
+        BreakthroughDirection::Forward,
+        BreakthroughDirection::Right,
+    ];
+
+    fn dx(&self) -> isize {
+        match self {
+            BreakthroughDirection::Left => -1,
+            BreakthroughDirection::Forward => 0,
+            BreakthroughDirection::Right => 1,
+        }
+    }
+}
+
+impl fmt::Display for BreakthroughDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BreakthroughDirection::Left => "left",
+            BreakthroughDirection::Forward => "forward",
+            BreakthroughDirection::Right => "right",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/*
+A move is a from-square and one of the three forward directions.
+The rank is counted from the mover's own home rank, so the same move index means the same
+thing for both players. This matches the mirrored board given to the network.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BreakthroughMove {
+    file: usize,
+    rank: usize,
+    direction: BreakthroughDirection,
+}
+
+impl fmt::Display for BreakthroughMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}-{}",
+            (b'a' + self.file as u8) as char,
+            self.rank + 1,
+            self.direction
+        )
+    }
+}
+
+/*
+Breakthrough on an N×N board, 8×8 by default and 6×6 for quicker experiments.
+Each side starts with its two home ranks filled and moves one square forward or diagonally forward,
+capturing only diagonally. The first player to reach the far rank, or to capture every enemy piece, wins.
+The game cannot end in a draw.
+ */
+#[derive(Debug, Clone)]
+pub struct Breakthrough<const N: usize = 8> {
+    board: [[Option<PlayerId>; N]; N],
+    playing: PlayerId,
+}
+
+impl<const N: usize> Breakthrough<N> {
+    // Converts a rank counted from the given player's home rank to a row of the board
+    fn absolute_rank(player: PlayerId, rank: usize) -> usize {
+        match player {
+            PlayerId::First => rank,
+            PlayerId::Second => N - 1 - rank,
+        }
+    }
+
+    fn count_pieces(&self, player: PlayerId) -> usize {
+        self.board
+            .iter()
+            .flatten()
+            .filter(|tile| **tile == Some(player))
+            .count()
+    }
+
+    fn reached_far_rank(&self, player: PlayerId) -> bool {
+        let far_rank = Self::absolute_rank(player, N - 1);
+        self.board[far_rank].iter().any(|tile| *tile == Some(player))
+    }
+
+    fn target(&self, m: &BreakthroughMove) -> Option<(usize, usize)> {
+        let x = m.file as isize + m.direction.dx();
+        if x < 0 || x >= N as isize || m.rank + 1 >= N {
+            return None;
+        }
+        Some((x as usize, Self::absolute_rank(self.playing, m.rank + 1)))
+    }
+
+    fn start() -> Self {
+        assert!(N >= 5, "Breakthrough needs at least five ranks");
+        let mut board = [[None; N]; N];
+        for x in 0..N {
+            board[0][x] = Some(PlayerId::First);
+            board[1][x] = Some(PlayerId::First);
+            board[N - 2][x] = Some(PlayerId::Second);
+            board[N - 1][x] = Some(PlayerId::Second);
+        }
+
+        Self {
+            board,
+            playing: PlayerId::First,
+        }
+    }
+
+    fn print_board(&self) {
+        for y in (0..N).rev() {
+            print!("{:>2}", y + 1);
+            for tile in self.board[y].iter() {
+                let icon = match tile {
+                    None => "·",
+                    Some(PlayerId::First) => "⚪",
+                    Some(PlayerId::Second) => "⚫",
+                };
+                print!(" {}", icon);
+            }
+            println!()
+        }
+        print!("  ");
+        for x in 0..N {
+            print!(" {}", (b'a' + x as u8) as char);
+        }
+        println!();
+        println!(
+            "Currently playing: {:?} (moves are written from the mover's side)",
+            self.playing
+        );
+    }
+
+    /*
+    The board is always seen from one side, the mover's for write_nn_input: channel 0 holds that
+    player's pieces, channel 1 the opponent's, and the ranks are flipped for the second player so that
+    they always advance up the board.
+     */
+    fn encode(&self, player: PlayerId, out: &mut [f32]) {
+        let mut data = Vec::with_capacity(3 * N * N);
+
+        for owner in [player, player.opponent()] {
+            for rank in 0..N {
+                let y = Self::absolute_rank(player, rank);
+                for x in 0..N {
+                    data.push(if self.board[y][x] == Some(owner) { 1.0 } else { 0.0 });
+                }
+            }
+        }
+
+        let player_num = match self.playing {
+            PlayerId::First => 1.0_f32,
+            PlayerId::Second => 0.0,
+        };
+        data.extend(std::iter::repeat(player_num).take(N * N));
+
+        out.copy_from_slice(&data);
+    }
+
+    fn moves(&self) -> Vec<BreakthroughMove> {
+        let mut moves = vec![];
+
+        for rank in 0..N {
+            let y = Self::absolute_rank(self.playing, rank);
+            for file in 0..N {
+                if self.board[y][file] != Some(self.playing) {
+                    continue;
+                }
+                for direction in BreakthroughDirection::ALL {
+                    let m = BreakthroughMove {
+                        file,
+                        rank,
+                        direction,
+                    };
+                    if let Some((tx, ty)) = self.target(&m) {
+                        let legal = match direction {
+                            // Straight moves can never capture
+                            BreakthroughDirection::Forward => self.board[ty][tx].is_none(),
+                            _ => self.board[ty][tx] != Some(self.playing),
+                        };
+                        if legal {
+                            moves.push(m);
+                        }
+                    }
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn play(&mut self, m: BreakthroughMove) {
+        let (tx, ty) = self.target(&m).expect("Move leaves the board");
+        let y = Self::absolute_rank(self.playing, m.rank);
+
+        self.board[y][m.file] = None;
+        self.board[ty][tx] = Some(self.playing);
+
+        self.playing = self.playing.opponent();
+    }
+
+    fn result(&self) -> Option<GameResult> {
+        for player in [PlayerId::First, PlayerId::Second] {
+            if self.reached_far_rank(player) || self.count_pieces(player.opponent()) == 0 {
+                return Some(GameResult::Winner(player));
+            }
+        }
+
+        // A fully blocked side loses, so there are still no draws
+        if self.moves().is_empty() {
+            return Some(GameResult::Winner(self.playing.opponent()));
+        }
+
+        None
+    }
+
+    fn move_at(i: usize) -> BreakthroughMove {
+        BreakthroughMove {
+            file: (i / 3) % N,
+            rank: (i / 3) / N,
+            direction: BreakthroughDirection::ALL[i % 3],
+        }
+    }
+}
+
+// The trait's sizes can't be computed from a const generic on this toolchain, so each board size gets its own impl,
+// with the area as a literal since the network's shapes only match sizes that are plain numbers
+macro_rules! impl_breakthrough {
+    ($n:literal, area $area:literal) => {
+        impl Game for Breakthrough<$n> {
+            type Move = BreakthroughMove;
+
+            type Board = [[Option<PlayerId>; $n]; $n];
+
+            type BoardSizeX = Const<$n>;
+            type BoardSizeY = Const<$n>;
+
+            type TotalBoardSize = Const<$area>;
+
+            const CHANNELS: usize = 3;
+
+            const NUM_PLAYERS: usize = 2;
+
+            const TOTAL_MOVES: usize = 3 * $n * $n;
+
+            fn new() -> Self {
+                Self::start()
+            }
+
+            fn print(&self) {
+                self.print_board()
+            }
+
+            fn write_nn_input(&self, out: &mut [f32]) {
+                self.encode(self.playing, out)
+            }
+
+            fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+                self.encode(player, out)
+            }
+
+            fn get_board(&self) -> Self::Board {
+                self.board
+            }
+
+            fn legal_moves(&self) -> Vec<Self::Move> {
+                self.moves()
+            }
+
+            fn make_move(&mut self, m: Self::Move) {
+                self.play(m)
+            }
+
+            fn is_over(&self) -> bool {
+                self.get_result().is_some()
+            }
+
+            fn get_result(&self) -> Option<GameResult> {
+                self.result()
+            }
+
+            fn current_player(&self) -> PlayerId {
+                self.playing
+            }
+
+            fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES] {
+                core::array::from_fn(Self::move_at)
+            }
+
+            fn associate_players(
+                players: Vec<&Strategy<Self>>,
+            ) -> std::collections::HashMap<PlayerId, &Strategy<Self>> {
+                let mut players_map = HashMap::new();
+                players_map.insert(PlayerId::First, players[0]);
+                players_map.insert(PlayerId::Second, players[1]);
+
+                players_map
+            }
+        }
+    };
+}
+
+impl_breakthrough!(6, area 36);
+impl_breakthrough!(8, area 64);
+
+#[cfg(test)]
+mod test {
+    use super::{Breakthrough, BreakthroughDirection, BreakthroughMove};
+    use rust_games_shared::{Game, GameResult, PlayerId};
+
+    #[test]
+    fn opening_moves() {
+        let g: Breakthrough = Breakthrough::new();
+        // Only the front rank can move: 6 central pieces with 3 moves, 2 edge pieces with 2
+        assert_eq!(g.legal_moves().len(), 22);
+    }
+
+    #[test]
+    fn moves_are_mirrored_for_second() {
+        let mut g: Breakthrough<6> = Breakthrough::new();
+        for file in [0, 1] {
+            g.make_move(BreakthroughMove {
+                file,
+                rank: 1,
+                direction: BreakthroughDirection::Forward,
+            });
+        }
+        assert_eq!(g.get_board()[2][0], Some(PlayerId::First));
+        assert_eq!(g.get_board()[3][1], Some(PlayerId::Second));
+        assert_eq!(g.get_board()[4][1], None);
+    }
+
+    #[test]
+    fn reaching_far_rank_wins() {
+        let mut g: Breakthrough<6> = Breakthrough::new();
+        g.board = [[None; 6]; 6];
+        g.board[4][2] = Some(PlayerId::First);
+        g.board[0][0] = Some(PlayerId::Second);
+        g.make_move(BreakthroughMove {
+            file: 2,
+            rank: 4,
+            direction: BreakthroughDirection::Right,
+        });
+        assert!(matches!(
+            g.get_result(),
+            Some(GameResult::Winner(PlayerId::First))
+        ));
+    }
+}
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use dfdx::prelude::*;
 use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Connect4State {
     Empty,
     First,
@@ -180,48 +180,33 @@ impl Game for Connect4 {
         println!("Currently playing: {}", to_state(&self.playing));
     }
 
-    fn to_nn_input(
-        &self,
-    ) -> Tensor<
-        (
-            Const<{ Self::CHANNELS }>,
-            Self::BoardSizeX,
-            Self::BoardSizeY,
-        ),
-        f32,
-        AutoDevice,
-    > {
-        let dev: AutoDevice = Default::default();
-        let mut o_channel = [[0.0; 8]; 7];
-        for (x, row) in self.board.iter().enumerate() {
-            for (y, tile) in row.iter().enumerate() {
-                o_channel[x][y] = match tile {
-                    Connect4State::Empty => 0.0,
-                    Connect4State::First => 0.0,
-                    Connect4State::Second => 1.0,
-                }
-            }
-        }
-
-        let mut x_channel = [[0.0; 8]; 7];
-        for (x, row) in self.board.iter().enumerate() {
-            for (y, tile) in row.iter().enumerate() {
-                x_channel[x][y] = match tile {
-                    Connect4State::Empty => 0.0,
-                    Connect4State::First => 1.0,
-                    Connect4State::Second => 0.0,
-                }
-            }
-        }
-
+    fn write_nn_input(&self, out: &mut [f32]) {
         let player_num = match self.playing {
             PlayerId::First => 1.0_f32,
             PlayerId::Second => 0.0,
         };
 
-        let player_channel = [[player_num; 8]; 7];
+        // Channels are the first player's pieces, the second player's pieces and the player to move.
+        // The board is stored row by row, while the network sees it column by column
+        for (y, row) in self.board.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                let i = x * 8 + y;
+                match tile {
+                    Connect4State::Empty => {}
+                    Connect4State::First => out[i] = 1.0,
+                    Connect4State::Second => out[56 + i] = 1.0,
+                }
+                out[112 + i] = player_num;
+            }
+        }
+    }
 
-        dev.tensor([x_channel, o_channel, player_channel])
+    fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+        self.write_nn_input(out);
+        if player == PlayerId::Second {
+            let (first_channel, rest) = out.split_at_mut(56);
+            first_channel.swap_with_slice(&mut rest[..56]);
+        }
     }
 
     fn get_board(&self) -> Self::Board {
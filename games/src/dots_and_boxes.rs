@@ -0,0 +1,369 @@
+use core::fmt;
+use std::{cmp::Ordering, collections::HashMap};
+
+use dfdx::prelude::*;
+use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
+
+/*
+An edge of the grid. Horizontal edges run along the top of box (x, y), so y goes up to H.
+Vertical edges run along the left of box (x, y), so x goes up to W.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DotsAndBoxesMove {
+    Horizontal(usize, usize),
+    Vertical(usize, usize),
+}
+
+impl fmt::Display for DotsAndBoxesMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DotsAndBoxesMove::Horizontal(x, y) => write!(f, "h {} {}", x + 1, y + 1),
+            DotsAndBoxesMove::Vertical(x, y) => write!(f, "v {} {}", x + 1, y + 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DotsAndBoxesBoard {
+    pub edges: Vec<bool>,
+    pub boxes: Vec<Option<PlayerId>>,
+}
+
+/*
+Dots and Boxes on a grid of W×H boxes, 3×3 by default. 1×1 and 2×2 grids are there for tests.
+Completing a box scores it and gives the same player another move, so turns do not strictly alternate.
+The player with more boxes at the end wins.
+ */
+#[derive(Debug, Clone)]
+pub struct DotsAndBoxes<const W: usize = 3, const H: usize = 3> {
+    board: DotsAndBoxesBoard,
+    playing: PlayerId,
+}
+
+impl<const W: usize, const H: usize> DotsAndBoxes<W, H> {
+    const NUM_EDGES: usize = 2 * W * H + W + H;
+
+    fn edge_index(m: &DotsAndBoxesMove) -> usize {
+        match *m {
+            DotsAndBoxesMove::Horizontal(x, y) => y * W + x,
+            DotsAndBoxesMove::Vertical(x, y) => W * (H + 1) + y * (W + 1) + x,
+        }
+    }
+
+    fn edge_from_index(i: usize) -> DotsAndBoxesMove {
+        if i < W * (H + 1) {
+            DotsAndBoxesMove::Horizontal(i % W, i / W)
+        } else {
+            let i = i - W * (H + 1);
+            DotsAndBoxesMove::Vertical(i % (W + 1), i / (W + 1))
+        }
+    }
+
+    fn has_edge(&self, m: DotsAndBoxesMove) -> bool {
+        self.board.edges[Self::edge_index(&m)]
+    }
+
+    fn box_complete(&self, x: usize, y: usize) -> bool {
+        self.has_edge(DotsAndBoxesMove::Horizontal(x, y))
+            && self.has_edge(DotsAndBoxesMove::Horizontal(x, y + 1))
+            && self.has_edge(DotsAndBoxesMove::Vertical(x, y))
+            && self.has_edge(DotsAndBoxesMove::Vertical(x + 1, y))
+    }
+
+    // The one or two boxes bordered by an edge
+    fn adjacent_boxes(m: &DotsAndBoxesMove) -> Vec<(usize, usize)> {
+        let mut boxes = vec![];
+        match *m {
+            DotsAndBoxesMove::Horizontal(x, y) => {
+                if y > 0 {
+                    boxes.push((x, y - 1));
+                }
+                if y < H {
+                    boxes.push((x, y));
+                }
+            }
+            DotsAndBoxesMove::Vertical(x, y) => {
+                if x > 0 {
+                    boxes.push((x - 1, y));
+                }
+                if x < W {
+                    boxes.push((x, y));
+                }
+            }
+        }
+        boxes
+    }
+
+    pub fn scores(&self) -> (usize, usize) {
+        let count = |player| {
+            self.board
+                .boxes
+                .iter()
+                .filter(|owner| **owner == Some(player))
+                .count()
+        };
+        (count(PlayerId::First), count(PlayerId::Second))
+    }
+
+    fn start() -> Self {
+        Self {
+            board: DotsAndBoxesBoard {
+                edges: vec![false; Self::NUM_EDGES],
+                boxes: vec![None; W * H],
+            },
+            playing: PlayerId::First,
+        }
+    }
+
+    fn print_board(&self) {
+        for y in 0..=H {
+            for x in 0..W {
+                print!("•");
+                if self.has_edge(DotsAndBoxesMove::Horizontal(x, y)) {
+                    print!("───");
+                } else {
+                    print!("   ");
+                }
+            }
+            println!("•");
+
+            if y == H {
+                break;
+            }
+            for x in 0..=W {
+                if self.has_edge(DotsAndBoxesMove::Vertical(x, y)) {
+                    print!("│");
+                } else {
+                    print!(" ");
+                }
+                if x < W {
+                    match self.board.boxes[y * W + x] {
+                        None => print!("   "),
+                        Some(PlayerId::First) => print!(" 1 "),
+                        Some(PlayerId::Second) => print!(" 2 "),
+                    }
+                }
+            }
+            println!();
+        }
+        let (first, second) = self.scores();
+        println!("Score: {} - {}", first, second);
+        println!("Currently playing: {:?}", self.playing);
+    }
+
+    fn encode(&self, out: &mut [f32]) {
+        let rows = 2 * H + 1;
+        let cols = 2 * W + 1;
+        let mut edge_channel = vec![0.0_f32; rows * cols];
+        let mut first_channel = vec![0.0_f32; rows * cols];
+        let mut second_channel = vec![0.0_f32; rows * cols];
+
+        for r in 0..rows {
+            for c in 0..cols {
+                let i = r * cols + c;
+                match (r % 2, c % 2) {
+                    (0, 1) => {
+                        if self.has_edge(DotsAndBoxesMove::Horizontal(c / 2, r / 2)) {
+                            edge_channel[i] = 1.0;
+                        }
+                    }
+                    (1, 0) => {
+                        if self.has_edge(DotsAndBoxesMove::Vertical(c / 2, r / 2)) {
+                            edge_channel[i] = 1.0;
+                        }
+                    }
+                    (1, 1) => match self.board.boxes[(r / 2) * W + c / 2] {
+                        None => {}
+                        Some(PlayerId::First) => first_channel[i] = 1.0,
+                        Some(PlayerId::Second) => second_channel[i] = 1.0,
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        let player_num = match self.playing {
+            PlayerId::First => 1.0_f32,
+            PlayerId::Second => 0.0,
+        };
+
+        let mut data = edge_channel;
+        data.extend(first_channel);
+        data.extend(second_channel);
+        data.extend(std::iter::repeat(player_num).take(rows * cols));
+
+        out.copy_from_slice(&data);
+    }
+
+    // Seen from the given player, whose boxes take the first box channel
+    fn encode_perspective(&self, player: PlayerId, out: &mut [f32]) {
+        self.encode(out);
+        if player == PlayerId::Second {
+            let plane = (2 * H + 1) * (2 * W + 1);
+            let (first_channel, rest) = out[plane..].split_at_mut(plane);
+            first_channel.swap_with_slice(&mut rest[..plane]);
+        }
+    }
+
+    fn moves(&self) -> Vec<DotsAndBoxesMove> {
+        (0..Self::NUM_EDGES)
+            .filter(|i| !self.board.edges[*i])
+            .map(Self::edge_from_index)
+            .collect()
+    }
+
+    fn play(&mut self, m: DotsAndBoxesMove) {
+        self.board.edges[Self::edge_index(&m)] = true;
+
+        let mut completed_box = false;
+        for (x, y) in Self::adjacent_boxes(&m) {
+            if self.box_complete(x, y) {
+                self.board.boxes[y * W + x] = Some(self.playing);
+                completed_box = true;
+            }
+        }
+
+        // Completing a box earns another move
+        if !completed_box {
+            self.playing = self.playing.opponent();
+        }
+    }
+
+    fn all_drawn(&self) -> bool {
+        self.board.edges.iter().all(|edge| *edge)
+    }
+
+    fn result(&self) -> Option<GameResult> {
+        if !self.all_drawn() {
+            return None;
+        }
+
+        let (first, second) = self.scores();
+        match first.cmp(&second) {
+            Ordering::Greater => Some(GameResult::Winner(PlayerId::First)),
+            Ordering::Equal => Some(GameResult::Tie),
+            Ordering::Less => Some(GameResult::Winner(PlayerId::Second)),
+        }
+    }
+}
+
+/*
+The trait's sizes can't be computed from const generics on this toolchain, so each grid size gets its own impl.
+The lattice size (2H + 1)×(2W + 1) and its area are given as literals too, since the network's shapes only
+match sizes that are plain numbers.
+ */
+macro_rules! impl_dots_and_boxes {
+    ($w:literal, $h:literal, lattice $rows:literal x $cols:literal = $area:literal) => {
+        impl Game for DotsAndBoxes<$w, $h> {
+            type Move = DotsAndBoxesMove;
+
+            type Board = DotsAndBoxesBoard;
+
+            // The network sees the dot lattice: dots on even/even points, edges between them, boxes on odd/odd
+            type BoardSizeX = Const<$rows>;
+            type BoardSizeY = Const<$cols>;
+
+            type TotalBoardSize = Const<$area>;
+
+            const CHANNELS: usize = 4;
+
+            const NUM_PLAYERS: usize = 2;
+
+            const TOTAL_MOVES: usize = 2 * $w * $h + $w + $h;
+
+            fn new() -> Self {
+                Self::start()
+            }
+
+            fn print(&self) {
+                self.print_board()
+            }
+
+            fn write_nn_input(&self, out: &mut [f32]) {
+                self.encode(out)
+            }
+
+            fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+                self.encode_perspective(player, out)
+            }
+
+            fn get_board(&self) -> Self::Board {
+                self.board.clone()
+            }
+
+            fn legal_moves(&self) -> Vec<Self::Move> {
+                self.moves()
+            }
+
+            fn make_move(&mut self, m: Self::Move) {
+                self.play(m)
+            }
+
+            fn is_over(&self) -> bool {
+                self.all_drawn()
+            }
+
+            fn get_result(&self) -> Option<GameResult> {
+                self.result()
+            }
+
+            fn score_margin(&self) -> Option<f32> {
+                let (first, second) = self.scores();
+                Some(first as f32 - second as f32)
+            }
+
+            fn current_player(&self) -> PlayerId {
+                self.playing
+            }
+
+            fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES] {
+                core::array::from_fn(Self::edge_from_index)
+            }
+
+            fn associate_players(
+                players: Vec<&Strategy<Self>>,
+            ) -> std::collections::HashMap<PlayerId, &Strategy<Self>> {
+                let mut players_map = HashMap::new();
+                players_map.insert(PlayerId::First, players[0]);
+                players_map.insert(PlayerId::Second, players[1]);
+
+                players_map
+            }
+        }
+    };
+}
+
+impl_dots_and_boxes!(1, 1, lattice 3 x 3 = 9);
+impl_dots_and_boxes!(2, 2, lattice 5 x 5 = 25);
+impl_dots_and_boxes!(3, 3, lattice 7 x 7 = 49);
+
+#[cfg(test)]
+mod test {
+    use super::{DotsAndBoxes, DotsAndBoxesMove};
+    use rust_games_shared::{Game, GameResult, PlayerId};
+
+    #[test]
+    fn edge_count() {
+        let g: DotsAndBoxes = DotsAndBoxes::new();
+        assert_eq!(g.legal_moves().len(), 24);
+        assert_eq!(DotsAndBoxes::<3, 3>::all_possible_moves().len(), 24);
+    }
+
+    #[test]
+    fn completing_a_box_keeps_the_turn() {
+        let mut g: DotsAndBoxes<1, 1> = DotsAndBoxes::new();
+        g.make_move(DotsAndBoxesMove::Horizontal(0, 0));
+        g.make_move(DotsAndBoxesMove::Horizontal(0, 1));
+        g.make_move(DotsAndBoxesMove::Vertical(0, 0));
+        assert_eq!(g.current_player(), PlayerId::Second);
+
+        g.make_move(DotsAndBoxesMove::Vertical(1, 0));
+        assert_eq!(g.current_player(), PlayerId::Second);
+        assert_eq!(g.scores(), (0, 1));
+        assert!(matches!(
+            g.get_result(),
+            Some(GameResult::Winner(PlayerId::Second))
+        ));
+        assert_eq!(g.score_margin(), Some(-1.0));
+    }
+}
@@ -0,0 +1,313 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use dfdx::prelude::*;
+use rust_games_shared::{Game, GameResult, ImperfectInformation, PlayerId, Strategy};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PokerCard {
+    Jack,
+    Queen,
+    King,
+}
+
+impl PokerCard {
+    pub(crate) const ALL: [PokerCard; 3] = [PokerCard::Jack, PokerCard::Queen, PokerCard::King];
+
+    pub(crate) fn index(&self) -> usize {
+        match self {
+            PokerCard::Jack => 0,
+            PokerCard::Queen => 1,
+            PokerCard::King => 2,
+        }
+    }
+}
+
+impl fmt::Display for PokerCard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            PokerCard::Jack => "J",
+            PokerCard::Queen => "Q",
+            PokerCard::King => "K",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KuhnMove {
+    // Chance move: the first and second player's cards
+    Deal(PokerCard, PokerCard),
+    // Check, or fold when facing a bet
+    Pass,
+    // Bet, or call when facing a bet
+    Bet,
+}
+
+impl fmt::Display for KuhnMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KuhnMove::Deal(first, second) => write!(f, "Deal {} {}", first, second),
+            KuhnMove::Pass => write!(f, "Pass"),
+            KuhnMove::Bet => write!(f, "Bet"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KuhnObservation {
+    pub card: Option<PokerCard>,
+    pub history: Vec<KuhnMove>,
+}
+
+/*
+Kuhn poker: three cards, one each, an ante of 1 and a single bet of 1.
+The deal is a chance move, and each player only observes their own card.
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KuhnPoker {
+    cards: Option<[PokerCard; 2]>,
+    history: Vec<KuhnMove>,
+}
+
+impl KuhnPoker {
+    // The winner and how many chips they take from the loser
+    fn outcome(&self) -> Option<(PlayerId, u8)> {
+        let cards = self.cards?;
+        let showdown_winner = if cards[0] > cards[1] {
+            PlayerId::First
+        } else {
+            PlayerId::Second
+        };
+
+        match self.history.as_slice() {
+            [KuhnMove::Pass, KuhnMove::Pass] => Some((showdown_winner, 1)),
+            [KuhnMove::Bet, KuhnMove::Pass] => Some((PlayerId::First, 1)),
+            [KuhnMove::Pass, KuhnMove::Bet, KuhnMove::Pass] => Some((PlayerId::Second, 1)),
+            [KuhnMove::Bet, KuhnMove::Bet] | [KuhnMove::Pass, KuhnMove::Bet, KuhnMove::Bet] => {
+                Some((showdown_winner, 2))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Game for KuhnPoker {
+    type Move = KuhnMove;
+
+    type Board = KuhnPoker;
+
+    // One column per card rank
+    type BoardSizeX = Const<1>;
+    type BoardSizeY = Const<3>;
+
+    type TotalBoardSize = Const<3>;
+
+    const CHANNELS: usize = 1 /* Own card */ + 3 /* Betting history */ + 1 /* Player */;
+
+    const NUM_PLAYERS: usize = 2;
+    const PERFECT_INFORMATION: bool = false;
+
+    const TOTAL_MOVES: usize = 6 /* Deals */ + 2 /* Actions */;
+
+    fn new() -> Self {
+        Self {
+            cards: None,
+            history: vec![],
+        }
+    }
+
+    // Only shows the current player's card
+    fn print(&self) {
+        let observation = self.observation(self.current_player());
+        match observation.card {
+            Some(card) => println!("Your card: {}", card),
+            None => println!("Cards not dealt yet"),
+        }
+        let history: Vec<String> = observation.history.iter().map(|m| m.to_string()).collect();
+        println!("Betting: {}", history.join(", "));
+        println!("Currently playing: {:?}", self.current_player());
+    }
+
+    fn write_nn_input(&self, out: &mut [f32]) {
+        self.write_observation_nn_input(self.current_player(), out)
+    }
+
+    fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+        self.write_observation_nn_input(player, out)
+    }
+
+    fn get_board(&self) -> Self::Board {
+        self.clone()
+    }
+
+    fn legal_moves(&self) -> Vec<Self::Move> {
+        if self.is_chance_node() {
+            self.chance_outcomes().into_iter().map(|(m, _)| m).collect()
+        } else if self.is_over() {
+            vec![]
+        } else {
+            vec![KuhnMove::Pass, KuhnMove::Bet]
+        }
+    }
+
+    fn make_move(&mut self, m: Self::Move) {
+        match m {
+            KuhnMove::Deal(first, second) => self.cards = Some([first, second]),
+            action => self.history.push(action),
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.outcome().is_some()
+    }
+
+    fn get_result(&self) -> Option<GameResult> {
+        self.outcome().map(|(winner, _)| GameResult::Winner(winner))
+    }
+
+    fn score_margin(&self) -> Option<f32> {
+        self.outcome().map(|(winner, chips)| match winner {
+            PlayerId::First => chips as f32,
+            PlayerId::Second => -(chips as f32),
+        })
+    }
+
+    fn current_player(&self) -> PlayerId {
+        if self.history.len() % 2 == 0 {
+            PlayerId::First
+        } else {
+            PlayerId::Second
+        }
+    }
+
+    fn is_chance_node(&self) -> bool {
+        self.cards.is_none()
+    }
+
+    fn chance_outcomes(&self) -> Vec<(Self::Move, f32)> {
+        let mut outcomes = vec![];
+        for first in PokerCard::ALL {
+            for second in PokerCard::ALL {
+                if first != second {
+                    outcomes.push((KuhnMove::Deal(first, second), 1.0 / 6.0));
+                }
+            }
+        }
+        outcomes
+    }
+
+    fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES] {
+        let mut moves = [KuhnMove::Pass; Self::TOTAL_MOVES];
+
+        let mut i = 0;
+        for first in PokerCard::ALL {
+            for second in PokerCard::ALL {
+                if first != second {
+                    moves[i] = KuhnMove::Deal(first, second);
+                    i += 1;
+                }
+            }
+        }
+        moves[i + 1] = KuhnMove::Bet;
+
+        moves
+    }
+
+    fn associate_players(
+        players: Vec<&Strategy<Self>>,
+    ) -> std::collections::HashMap<PlayerId, &Strategy<Self>> {
+        let mut players_map = HashMap::new();
+        players_map.insert(PlayerId::First, players[0]);
+        players_map.insert(PlayerId::Second, players[1]);
+
+        players_map
+    }
+}
+
+impl ImperfectInformation for KuhnPoker {
+    type Observation = KuhnObservation;
+
+    fn observation(&self, player: PlayerId) -> Self::Observation {
+        KuhnObservation {
+            card: self.cards.map(|cards| cards[usize::from(player)]),
+            history: self.history.clone(),
+        }
+    }
+
+    fn write_observation_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+        let observation = self.observation(player);
+
+        let mut card_channel = [0.0_f32; 3];
+        if let Some(card) = observation.card {
+            card_channel[card.index()] = 1.0;
+        }
+
+        // Each betting slot is one-hot over (pass, bet, empty)
+        let mut history_channels = [[0.0_f32; 3]; 3];
+        for (slot, channel) in history_channels.iter_mut().enumerate() {
+            match observation.history.get(slot) {
+                Some(KuhnMove::Pass) => channel[0] = 1.0,
+                Some(KuhnMove::Bet) => channel[1] = 1.0,
+                _ => channel[2] = 1.0,
+            }
+        }
+
+        let player_num = match player {
+            PlayerId::First => 1.0_f32,
+            PlayerId::Second => 0.0,
+        };
+
+        let channels = [
+            card_channel,
+            history_channels[0],
+            history_channels[1],
+            history_channels[2],
+            [player_num; 3],
+        ];
+        for (channel_out, channel) in out.chunks_mut(3).zip(channels.iter()) {
+            channel_out.copy_from_slice(channel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{KuhnMove, KuhnPoker, PokerCard};
+    use rust_games_shared::{Game, GameResult, ImperfectInformation, PlayerId};
+
+    #[test]
+    fn observations_hide_opponent_card() {
+        let mut g = KuhnPoker::new();
+        assert!(g.is_chance_node());
+        g.make_move(KuhnMove::Deal(PokerCard::King, PokerCard::Jack));
+        assert_eq!(g.observation(PlayerId::First).card, Some(PokerCard::King));
+        assert_eq!(g.observation(PlayerId::Second).card, Some(PokerCard::Jack));
+    }
+
+    #[test]
+    fn fold_to_bet() {
+        let mut g = KuhnPoker::new();
+        g.make_move(KuhnMove::Deal(PokerCard::Jack, PokerCard::King));
+        g.make_move(KuhnMove::Bet);
+        assert_eq!(g.current_player(), PlayerId::Second);
+        g.make_move(KuhnMove::Pass);
+        assert!(matches!(
+            g.get_result(),
+            Some(GameResult::Winner(PlayerId::First))
+        ));
+        assert_eq!(g.score_margin(), Some(1.0));
+    }
+
+    #[test]
+    fn called_showdown() {
+        let mut g = KuhnPoker::new();
+        g.make_move(KuhnMove::Deal(PokerCard::Jack, PokerCard::Queen));
+        g.make_move(KuhnMove::Pass);
+        g.make_move(KuhnMove::Bet);
+        assert!(!g.is_over());
+        g.make_move(KuhnMove::Bet);
+        assert_eq!(g.score_margin(), Some(-2.0));
+    }
+}
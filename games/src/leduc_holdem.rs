@@ -0,0 +1,410 @@
+use core::fmt;
+use std::{cmp::Ordering, collections::HashMap};
+
+use dfdx::prelude::*;
+use rust_games_shared::{Game, GameResult, ImperfectInformation, PlayerId, Strategy};
+
+use crate::kuhn_poker::PokerCard;
+
+const BET_SIZES: [u8; 2] = [2, 4];
+const MAX_RAISES: usize = 2;
+// Check, raise, re-raise, call
+const MAX_ACTIONS_PER_ROUND: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeducMove {
+    // Chance move: the ranks of the first and second player's private cards
+    DealPrivate(PokerCard, PokerCard),
+    // Chance move: the public card before the second betting round
+    DealPublic(PokerCard),
+    Fold,
+    // Check, or call when facing a bet
+    Call,
+    // Bet, or raise when facing a bet
+    Raise,
+}
+
+impl fmt::Display for LeducMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeducMove::DealPrivate(first, second) => write!(f, "Deal {} {}", first, second),
+            LeducMove::DealPublic(card) => write!(f, "Public {}", card),
+            LeducMove::Fold => write!(f, "Fold"),
+            LeducMove::Call => write!(f, "Call"),
+            LeducMove::Raise => write!(f, "Raise"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LeducObservation {
+    pub card: Option<PokerCard>,
+    pub public: Option<PokerCard>,
+    pub rounds: [Vec<LeducMove>; 2],
+}
+
+/*
+Leduc hold'em: a six card deck (two of each rank), one private card each, and a public card
+dealt between the two betting rounds. Bets are 2 in the first round and 4 in the second,
+with at most two raises per round. A pair with the public card beats any unpaired hand.
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LeducHoldem {
+    private: Option<[PokerCard; 2]>,
+    public: Option<PokerCard>,
+    // 0 and 1 are the betting rounds, 2 means the hands are shown down
+    round: usize,
+    rounds: [Vec<LeducMove>; 2],
+    contributions: [u8; 2],
+    folded: Option<PlayerId>,
+    playing: PlayerId,
+}
+
+impl LeducHoldem {
+    fn raises_this_round(&self) -> usize {
+        self.rounds[self.round]
+            .iter()
+            .filter(|m| **m == LeducMove::Raise)
+            .count()
+    }
+
+    fn facing_bet(&self) -> bool {
+        let p = usize::from(self.playing);
+        self.contributions[p] < self.contributions[1 - p]
+    }
+
+    fn hand_strength(&self, player: PlayerId) -> usize {
+        let card = self.private.expect("Cards were not dealt")[usize::from(player)];
+        let public = self.public.expect("Public card was not dealt");
+        if card == public {
+            10 + card.index()
+        } else {
+            card.index()
+        }
+    }
+
+    // The winner (None for a split pot) and how many chips change hands
+    fn outcome(&self) -> Option<(Option<PlayerId>, u8)> {
+        if let Some(folder) = self.folded {
+            return Some((
+                Some(folder.opponent()),
+                self.contributions[usize::from(folder)],
+            ));
+        }
+        if self.round < 2 {
+            return None;
+        }
+
+        let first = self.hand_strength(PlayerId::First);
+        let second = self.hand_strength(PlayerId::Second);
+        let winner = match first.cmp(&second) {
+            Ordering::Greater => Some(PlayerId::First),
+            Ordering::Less => Some(PlayerId::Second),
+            Ordering::Equal => None,
+        };
+        Some((winner, self.contributions[0]))
+    }
+}
+
+impl Game for LeducHoldem {
+    type Move = LeducMove;
+
+    type Board = LeducHoldem;
+
+    // One column per card rank
+    type BoardSizeX = Const<1>;
+    type BoardSizeY = Const<3>;
+
+    type TotalBoardSize = Const<3>;
+
+    const CHANNELS: usize = 2 /* Own and public card */
+        + 2 * MAX_ACTIONS_PER_ROUND /* Betting history */
+        + 2 /* Pot */
+        + 1 /* Player */;
+
+    const NUM_PLAYERS: usize = 2;
+    const PERFECT_INFORMATION: bool = false;
+
+    const TOTAL_MOVES: usize = 9 /* Private deals */ + 3 /* Public deals */ + 3 /* Actions */;
+
+    fn new() -> Self {
+        Self {
+            private: None,
+            public: None,
+            round: 0,
+            rounds: [vec![], vec![]],
+            contributions: [1, 1],
+            folded: None,
+            playing: PlayerId::First,
+        }
+    }
+
+    // Only shows what the current player can see
+    fn print(&self) {
+        let observation = self.observation(self.playing);
+        match observation.card {
+            Some(card) => println!("Your card: {}", card),
+            None => println!("Cards not dealt yet"),
+        }
+        if let Some(public) = observation.public {
+            println!("Public card: {}", public);
+        }
+        for (i, round) in observation.rounds.iter().enumerate() {
+            let actions: Vec<String> = round.iter().map(|m| m.to_string()).collect();
+            println!("Round {}: {}", i + 1, actions.join(", "));
+        }
+        println!(
+            "Pot: {} + {}",
+            self.contributions[0], self.contributions[1]
+        );
+        println!("Currently playing: {:?}", self.playing);
+    }
+
+    fn write_nn_input(&self, out: &mut [f32]) {
+        self.write_observation_nn_input(self.playing, out)
+    }
+
+    fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+        self.write_observation_nn_input(player, out)
+    }
+
+    fn get_board(&self) -> Self::Board {
+        self.clone()
+    }
+
+    fn legal_moves(&self) -> Vec<Self::Move> {
+        if self.is_chance_node() {
+            return self.chance_outcomes().into_iter().map(|(m, _)| m).collect();
+        }
+        if self.is_over() {
+            return vec![];
+        }
+
+        let mut moves = vec![];
+        if self.facing_bet() {
+            moves.push(LeducMove::Fold);
+        }
+        moves.push(LeducMove::Call);
+        if self.raises_this_round() < MAX_RAISES {
+            moves.push(LeducMove::Raise);
+        }
+        moves
+    }
+
+    fn make_move(&mut self, m: Self::Move) {
+        let p = usize::from(self.playing);
+        match m {
+            LeducMove::DealPrivate(first, second) => {
+                self.private = Some([first, second]);
+                return;
+            }
+            LeducMove::DealPublic(card) => {
+                self.public = Some(card);
+                return;
+            }
+            LeducMove::Fold => {
+                self.folded = Some(self.playing);
+            }
+            LeducMove::Call => {
+                self.contributions[p] = self.contributions[1 - p];
+            }
+            LeducMove::Raise => {
+                self.contributions[p] = self.contributions[1 - p] + BET_SIZES[self.round];
+            }
+        }
+        self.rounds[self.round].push(m);
+
+        // A call ends the round unless it was the opening check
+        let round_over = m == LeducMove::Call && self.rounds[self.round].len() >= 2;
+        if round_over {
+            self.round += 1;
+            self.playing = PlayerId::First;
+        } else {
+            self.playing = self.playing.opponent();
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.outcome().is_some()
+    }
+
+    fn get_result(&self) -> Option<GameResult> {
+        self.outcome().map(|(winner, _)| match winner {
+            Some(winner) => GameResult::Winner(winner),
+            None => GameResult::Tie,
+        })
+    }
+
+    fn score_margin(&self) -> Option<f32> {
+        self.outcome().map(|(winner, chips)| match winner {
+            Some(PlayerId::First) => chips as f32,
+            Some(PlayerId::Second) => -(chips as f32),
+            None => 0.0,
+        })
+    }
+
+    fn current_player(&self) -> PlayerId {
+        self.playing
+    }
+
+    fn is_chance_node(&self) -> bool {
+        self.folded.is_none()
+            && (self.private.is_none() || (self.round == 1 && self.public.is_none()))
+    }
+
+    fn chance_outcomes(&self) -> Vec<(Self::Move, f32)> {
+        let mut outcomes = vec![];
+        match self.private {
+            None => {
+                for first in PokerCard::ALL {
+                    for second in PokerCard::ALL {
+                        // Two of each rank in a six card deck
+                        let prob = if first == second {
+                            (2.0 / 6.0) * (1.0 / 5.0)
+                        } else {
+                            (2.0 / 6.0) * (2.0 / 5.0)
+                        };
+                        outcomes.push((LeducMove::DealPrivate(first, second), prob));
+                    }
+                }
+            }
+            Some(private) => {
+                for card in PokerCard::ALL {
+                    let left = 2 - private.iter().filter(|c| **c == card).count();
+                    if left > 0 {
+                        outcomes.push((LeducMove::DealPublic(card), left as f32 / 4.0));
+                    }
+                }
+            }
+        }
+        outcomes
+    }
+
+    fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES] {
+        let mut moves = [LeducMove::Fold; Self::TOTAL_MOVES];
+
+        let mut i = 0;
+        for first in PokerCard::ALL {
+            for second in PokerCard::ALL {
+                moves[i] = LeducMove::DealPrivate(first, second);
+                i += 1;
+            }
+        }
+        for card in PokerCard::ALL {
+            moves[i] = LeducMove::DealPublic(card);
+            i += 1;
+        }
+        moves[i + 1] = LeducMove::Call;
+        moves[i + 2] = LeducMove::Raise;
+
+        moves
+    }
+
+    fn associate_players(
+        players: Vec<&Strategy<Self>>,
+    ) -> std::collections::HashMap<PlayerId, &Strategy<Self>> {
+        let mut players_map = HashMap::new();
+        players_map.insert(PlayerId::First, players[0]);
+        players_map.insert(PlayerId::Second, players[1]);
+
+        players_map
+    }
+}
+
+impl ImperfectInformation for LeducHoldem {
+    type Observation = LeducObservation;
+
+    fn observation(&self, player: PlayerId) -> Self::Observation {
+        LeducObservation {
+            card: self.private.map(|cards| cards[usize::from(player)]),
+            public: self.public,
+            rounds: self.rounds.clone(),
+        }
+    }
+
+    fn write_observation_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+        let observation = self.observation(player);
+        let mut data = Vec::with_capacity(Self::CHANNELS * 3);
+
+        for card in [observation.card, observation.public] {
+            let mut channel = [0.0_f32; 3];
+            if let Some(card) = card {
+                channel[card.index()] = 1.0;
+            }
+            data.extend(channel);
+        }
+
+        // Each betting slot is one-hot over (call, raise, empty)
+        for round in observation.rounds.iter() {
+            for slot in 0..MAX_ACTIONS_PER_ROUND {
+                let mut channel = [0.0_f32; 3];
+                match round.get(slot) {
+                    Some(LeducMove::Call) => channel[0] = 1.0,
+                    Some(LeducMove::Raise) => channel[1] = 1.0,
+                    _ => channel[2] = 1.0,
+                }
+                data.extend(channel);
+            }
+        }
+
+        // The largest possible contribution is 1 + 2 * 2 + 2 * 4
+        for contribution in self.contributions {
+            data.extend([contribution as f32 / 13.0; 3]);
+        }
+
+        let player_num = match player {
+            PlayerId::First => 1.0_f32,
+            PlayerId::Second => 0.0,
+        };
+        data.extend([player_num; 3]);
+
+        out.copy_from_slice(&data);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LeducHoldem, LeducMove};
+    use crate::kuhn_poker::PokerCard;
+    use rust_games_shared::{Game, GameResult, PlayerId};
+
+    #[test]
+    fn deal_probabilities() {
+        let mut g = LeducHoldem::new();
+        let total: f32 = g.chance_outcomes().iter().map(|(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-5);
+
+        g.make_move(LeducMove::DealPrivate(PokerCard::King, PokerCard::King));
+        let public = g.chance_outcomes();
+        assert_eq!(public.len(), 2);
+    }
+
+    #[test]
+    fn pair_wins_showdown() {
+        let mut g = LeducHoldem::new();
+        g.make_move(LeducMove::DealPrivate(PokerCard::Jack, PokerCard::King));
+        g.make_move(LeducMove::Raise);
+        g.make_move(LeducMove::Call);
+        assert!(g.is_chance_node());
+        g.make_move(LeducMove::DealPublic(PokerCard::Jack));
+        assert_eq!(g.current_player(), PlayerId::First);
+        g.make_move(LeducMove::Call);
+        g.make_move(LeducMove::Call);
+        assert!(matches!(
+            g.get_result(),
+            Some(GameResult::Winner(PlayerId::First))
+        ));
+        assert_eq!(g.score_margin(), Some(3.0));
+    }
+
+    #[test]
+    fn raises_are_capped() {
+        let mut g = LeducHoldem::new();
+        g.make_move(LeducMove::DealPrivate(PokerCard::Jack, PokerCard::Queen));
+        g.make_move(LeducMove::Raise);
+        g.make_move(LeducMove::Raise);
+        assert!(!g.legal_moves().contains(&LeducMove::Raise));
+        g.make_move(LeducMove::Fold);
+        assert_eq!(g.score_margin(), Some(-3.0));
+    }
+}
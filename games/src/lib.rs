@@ -1,7 +1,99 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+/*
+Calls the given macro with every game the wrappers (Misere, MoveLimit, WithHistory) are implemented for.
+Their Game impls can't be generic over the wrapped game on this toolchain, so each one is written out.
+New games and sizes go here too, which the test at the bottom of this file checks.
+ */
+macro_rules! for_each_wrapped_game {
+    ($impl:ident $(, $arg:tt)*) => {
+        $impl!(
+            [$($arg),*]
+            TicTacToe,
+            Connect4,
+            Othello,
+            Breakthrough<6>,
+            Breakthrough<8>,
+            DotsAndBoxes<1, 1>,
+            DotsAndBoxes<2, 2>,
+            DotsAndBoxes<3, 3>,
+            Amazons<6>,
+            Amazons<10>,
+            Quoridor,
+            BlokusDuo,
+            Backgammon,
+        );
+    };
+}
+
+mod amazons;
+mod backgammon;
+mod blokus_duo;
+mod breakthrough;
 mod connect4;
+mod dots_and_boxes;
+mod kuhn_poker;
+mod leduc_holdem;
+mod misere;
+mod move_limit;
 mod othello;
+mod quoridor;
 mod tictactoe;
+mod with_history;
 
-pub use connect4::Connect4;
+pub use amazons::{Amazons, AmazonsBoard, AmazonsMove, AmazonsTile};
+pub use backgammon::{Backgammon, BackgammonBoard, BackgammonMove};
+pub use blokus_duo::{BlokusBoard, BlokusDuo, BlokusMove};
+pub use breakthrough::{Breakthrough, BreakthroughDirection, BreakthroughMove};
+pub use connect4::{Connect4, Connect4Move, Connect4State};
+pub use dots_and_boxes::{DotsAndBoxes, DotsAndBoxesBoard, DotsAndBoxesMove};
+pub use kuhn_poker::{KuhnMove, KuhnObservation, KuhnPoker, PokerCard};
+pub use leduc_holdem::{LeducHoldem, LeducMove, LeducObservation};
+pub use misere::Misere;
+pub use move_limit::{AdjudicateByScore, AdjudicateTie, Adjudicator, MoveLimit};
 pub use othello::{Othello, OthelloMove, OthelloState};
-pub use tictactoe::{TTTMove, TicTacToe};
+pub use quoridor::{Quoridor, QuoridorBoard, QuoridorMove};
+pub use tictactoe::{TTTMove, TTTState, TicTacToe};
+pub use with_history::WithHistory;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_games_shared::Game;
+
+    // Only compiles if all the wrappers are implemented for G
+    fn wrappable<G: Game>()
+    where
+        Misere<G>: Game,
+        MoveLimit<G, 100>: Game,
+        WithHistory<G, 2>: Game,
+    {
+    }
+
+    macro_rules! check_wrappable {
+        ($($game:ty),* $(,)?) => {
+            $(wrappable::<$game>();)*
+        };
+    }
+
+    // Every game and size in the crate, except the poker games, whose observations the wrappers don't pass on
+    #[test]
+    fn every_game_can_be_wrapped() {
+        check_wrappable!(
+            TicTacToe,
+            Connect4,
+            Othello,
+            Breakthrough<6>,
+            Breakthrough<8>,
+            DotsAndBoxes<1, 1>,
+            DotsAndBoxes<2, 2>,
+            DotsAndBoxes<3, 3>,
+            Amazons<6>,
+            Amazons<10>,
+            Quoridor,
+            BlokusDuo,
+            Backgammon,
+        );
+    }
+}
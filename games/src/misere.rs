@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
+
+use crate::{
+    Amazons, Backgammon, BlokusDuo, Breakthrough, Connect4, DotsAndBoxes, Othello, Quoridor, TicTacToe,
+};
+
+/*
+Plays a two player game with the result inverted, so whoever would have won loses.
+Moves, boards and the network encoding are all the wrapped game's.
+It's implemented for the games listed in for_each_wrapped_game in lib.rs, so new games must be added there.
+ */
+#[derive(Debug, Clone)]
+pub struct Misere<G: Game> {
+    game: G,
+}
+
+impl<G: Game> Misere<G> {
+    pub fn new_from_game(game: G) -> Self {
+        Self { game }
+    }
+
+    pub fn inner(&self) -> &G {
+        &self.game
+    }
+}
+
+impl<G: Game> Misere<G> {
+    fn inverted_result(&self) -> Option<GameResult> {
+        self.game.get_result().map(|result| match result {
+            GameResult::Winner(winner) => GameResult::Winner(winner.opponent()),
+            GameResult::Tie => GameResult::Tie,
+            GameResult::Ranking(mut ranking) => {
+                ranking.reverse();
+                GameResult::Ranking(ranking)
+            }
+        })
+    }
+}
+
+macro_rules! impl_misere {
+    ([] $($game:ty),* $(,)?) => {
+        $(
+            impl Game for Misere<$game> {
+                type Move = <$game as Game>::Move;
+
+                type Board = <$game as Game>::Board;
+
+                type BoardSizeX = <$game as Game>::BoardSizeX;
+                type BoardSizeY = <$game as Game>::BoardSizeY;
+
+                type TotalBoardSize = <$game as Game>::TotalBoardSize;
+
+                const CHANNELS: usize = <$game as Game>::CHANNELS;
+
+                const NUM_PLAYERS: usize = <$game as Game>::NUM_PLAYERS;
+
+                const PERFECT_INFORMATION: bool = <$game as Game>::PERFECT_INFORMATION;
+
+                const TOTAL_MOVES: usize = <$game as Game>::TOTAL_MOVES;
+
+                fn new() -> Self {
+                    assert!(Self::NUM_PLAYERS == 2, "Misère play needs a two player game");
+                    Self { game: <$game>::new() }
+                }
+
+                fn print(&self) {
+                    self.game.print();
+                    println!("(Misère: the usual winner loses)");
+                }
+
+                fn write_nn_input(&self, out: &mut [f32]) {
+                    self.game.write_nn_input(out)
+                }
+
+                fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+                    self.game.write_perspective_nn_input(player, out)
+                }
+
+                fn get_board(&self) -> Self::Board {
+                    self.game.get_board()
+                }
+
+                fn legal_moves(&self) -> Vec<Self::Move> {
+                    self.game.legal_moves()
+                }
+
+                fn make_move(&mut self, m: Self::Move) {
+                    self.game.make_move(m)
+                }
+
+                fn is_over(&self) -> bool {
+                    self.game.is_over()
+                }
+
+                fn get_result(&self) -> Option<GameResult> {
+                    self.inverted_result()
+                }
+
+                fn score_margin(&self) -> Option<f32> {
+                    self.game.score_margin().map(|margin| -margin)
+                }
+
+                fn current_player(&self) -> PlayerId {
+                    self.game.current_player()
+                }
+
+                fn is_chance_node(&self) -> bool {
+                    self.game.is_chance_node()
+                }
+
+                fn chance_outcomes(&self) -> Vec<(Self::Move, f32)> {
+                    self.game.chance_outcomes()
+                }
+
+                fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES] {
+                    <$game>::all_possible_moves()
+                }
+
+                fn associate_players(
+                    players: Vec<&Strategy<Self>>,
+                ) -> std::collections::HashMap<PlayerId, &Strategy<Self>> {
+                    let mut players_map = HashMap::new();
+                    players_map.insert(PlayerId::First, players[0]);
+                    players_map.insert(PlayerId::Second, players[1]);
+
+                    players_map
+                }
+            }
+        )*
+    };
+}
+
+for_each_wrapped_game!(impl_misere);
+
+#[cfg(test)]
+mod test {
+    use super::Misere;
+    use crate::TicTacToe;
+    use rust_games_shared::{Game, GameResult, PlayerId};
+
+    #[test]
+    fn three_in_a_row_loses() {
+        let mut g: Misere<TicTacToe> = Misere::new();
+        let moves = TicTacToe::all_possible_moves();
+        // X takes the top row while O plays in the middle row
+        for i in [0, 3, 1, 4, 2] {
+            g.make_move(moves[i]);
+        }
+        assert!(matches!(
+            g.get_result(),
+            Some(GameResult::Winner(PlayerId::Second))
+        ));
+    }
+}
@@ -0,0 +1,244 @@
+use std::{collections::HashMap, fmt::Debug};
+
+use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
+
+use crate::{
+    Amazons, Backgammon, BlokusDuo, Breakthrough, Connect4, DotsAndBoxes, Othello, Quoridor, TicTacToe,
+};
+
+/*
+Decides the result of a game that ran out of moves under a MoveLimit.
+Adjudicators are values, so one can carry an evaluator (see AdjudicateByEval in the players crate) or a model;
+MoveLimit::new uses the Default one.
+ */
+pub trait Adjudicator<G: Game>: Clone + Debug + Default {
+    fn adjudicate(&self, game: &G) -> GameResult;
+}
+
+// Unfinished games are draws
+#[derive(Debug, Clone, Default)]
+pub struct AdjudicateTie;
+
+impl<G: Game> Adjudicator<G> for AdjudicateTie {
+    fn adjudicate(&self, _game: &G) -> GameResult {
+        GameResult::Tie
+    }
+}
+
+// Unfinished games go to whoever leads on Game::score_margin, and are draws for games without a score
+#[derive(Debug, Clone, Default)]
+pub struct AdjudicateByScore;
+
+impl<G: Game> Adjudicator<G> for AdjudicateByScore {
+    fn adjudicate(&self, game: &G) -> GameResult {
+        match game.score_margin() {
+            Some(margin) if margin > 0.0 => GameResult::Winner(PlayerId::First),
+            Some(margin) if margin < 0.0 => GameResult::Winner(PlayerId::Second),
+            _ => GameResult::Tie,
+        }
+    }
+}
+
+/*
+Ends any game after MAX_PLIES player moves (chance moves do not count), and lets the adjudicator decide the result.
+The network sees the wrapped game's channels plus one more holding the fraction of the limit used,
+so it can learn about the horizon.
+ */
+#[derive(Debug, Clone)]
+pub struct MoveLimit<G: Game, const MAX_PLIES: usize, A: Adjudicator<G> = AdjudicateTie> {
+    game: G,
+    plies: usize,
+    adjudicator: A,
+}
+
+impl<G: Game, const MAX_PLIES: usize, A: Adjudicator<G>> MoveLimit<G, MAX_PLIES, A> {
+    pub fn new_from_game(game: G) -> Self {
+        Self::with_adjudicator(game, A::default())
+    }
+
+    pub fn with_adjudicator(game: G, adjudicator: A) -> Self {
+        Self {
+            game,
+            plies: 0,
+            adjudicator,
+        }
+    }
+
+    pub fn inner(&self) -> &G {
+        &self.game
+    }
+
+    pub fn plies(&self) -> usize {
+        self.plies
+    }
+
+    fn out_of_moves(&self) -> bool {
+        self.plies >= MAX_PLIES
+    }
+
+    fn write_with_ply_plane(&self, out: &mut [f32], write: impl Fn(&G, &mut [f32])) {
+        let (inner, ply_plane) = out.split_at_mut(G::nn_input_len());
+        write(&self.game, inner);
+        // The extra plane after the wrapped game's encoding holds the fraction of the limit used
+        ply_plane.fill(self.plies as f32 / MAX_PLIES as f32);
+    }
+
+    fn moves(&self) -> Vec<G::Move> {
+        if self.out_of_moves() {
+            return vec![];
+        }
+        self.game.legal_moves()
+    }
+
+    fn play(&mut self, m: G::Move) {
+        if !self.game.is_chance_node() {
+            self.plies += 1;
+        }
+        self.game.make_move(m);
+    }
+
+    fn result(&self) -> Option<GameResult> {
+        if self.game.is_over() {
+            return self.game.get_result();
+        }
+        if self.out_of_moves() {
+            return Some(self.adjudicator.adjudicate(&self.game));
+        }
+        None
+    }
+}
+
+// The wrapped game is spelled out in each impl, see for_each_wrapped_game
+macro_rules! impl_move_limit {
+    ([] $($game:ty),* $(,)?) => {
+        $(
+            impl<const MAX_PLIES: usize, A: Adjudicator<$game>> Game for MoveLimit<$game, MAX_PLIES, A> {
+                type Move = <$game as Game>::Move;
+
+                // The ply count is part of the position, since the same board is worth less closer to the limit
+                type Board = (<$game as Game>::Board, usize);
+
+                type BoardSizeX = <$game as Game>::BoardSizeX;
+                type BoardSizeY = <$game as Game>::BoardSizeY;
+
+                type TotalBoardSize = <$game as Game>::TotalBoardSize;
+
+                const CHANNELS: usize = <$game as Game>::CHANNELS + 1;
+
+                const NUM_PLAYERS: usize = <$game as Game>::NUM_PLAYERS;
+
+                const PERFECT_INFORMATION: bool = <$game as Game>::PERFECT_INFORMATION;
+
+                const TOTAL_MOVES: usize = <$game as Game>::TOTAL_MOVES;
+
+                fn new() -> Self {
+                    Self::new_from_game(<$game>::new())
+                }
+
+                fn print(&self) {
+                    self.game.print();
+                    println!("Ply {} of {}", self.plies, MAX_PLIES);
+                }
+
+                fn write_nn_input(&self, out: &mut [f32]) {
+                    self.write_with_ply_plane(out, |game, out| game.write_nn_input(out));
+                }
+
+                fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+                    self.write_with_ply_plane(out, |game, out| game.write_perspective_nn_input(player, out));
+                }
+
+                fn get_board(&self) -> Self::Board {
+                    (self.game.get_board(), self.plies)
+                }
+
+                fn legal_moves(&self) -> Vec<Self::Move> {
+                    self.moves()
+                }
+
+                fn make_move(&mut self, m: Self::Move) {
+                    self.play(m)
+                }
+
+                fn is_over(&self) -> bool {
+                    self.game.is_over() || self.out_of_moves()
+                }
+
+                fn get_result(&self) -> Option<GameResult> {
+                    self.result()
+                }
+
+                fn score_margin(&self) -> Option<f32> {
+                    self.game.score_margin()
+                }
+
+                fn current_player(&self) -> PlayerId {
+                    self.game.current_player()
+                }
+
+                fn is_chance_node(&self) -> bool {
+                    !self.out_of_moves() && self.game.is_chance_node()
+                }
+
+                fn chance_outcomes(&self) -> Vec<(Self::Move, f32)> {
+                    self.game.chance_outcomes()
+                }
+
+                fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES] {
+                    <$game>::all_possible_moves()
+                }
+
+                fn associate_players(
+                    players: Vec<&Strategy<Self>>,
+                ) -> std::collections::HashMap<PlayerId, &Strategy<Self>> {
+                    let mut players_map = HashMap::new();
+                    players_map.insert(PlayerId::First, players[0]);
+                    players_map.insert(PlayerId::Second, players[1]);
+
+                    players_map
+                }
+            }
+        )*
+    };
+}
+
+for_each_wrapped_game!(impl_move_limit);
+
+#[cfg(test)]
+mod test {
+    use super::{AdjudicateByScore, MoveLimit};
+    use crate::{Connect4, DotsAndBoxes, DotsAndBoxesMove};
+    use rust_games_shared::{Game, GameResult, PlayerId};
+
+    #[test]
+    fn limit_ends_the_game() {
+        let mut g: MoveLimit<Connect4, 4> = MoveLimit::new();
+        while !g.is_over() {
+            let m = g.legal_moves()[0];
+            g.make_move(m);
+        }
+        assert_eq!(g.plies(), 4);
+        assert!(matches!(g.get_result(), Some(GameResult::Tie)));
+        // Connect4 has 3 channels of 7×8, plus the ply plane
+        assert_eq!(g.to_nn_input().as_vec().len(), 4 * 56);
+    }
+
+    #[test]
+    fn adjudicate_by_score() {
+        // The second player closes the first box on the fourth move, when the limit is reached
+        let mut g: MoveLimit<DotsAndBoxes<2, 2>, 4, AdjudicateByScore> = MoveLimit::new();
+        for m in [
+            DotsAndBoxesMove::Horizontal(0, 0),
+            DotsAndBoxesMove::Horizontal(0, 1),
+            DotsAndBoxesMove::Vertical(0, 0),
+            DotsAndBoxesMove::Vertical(1, 0),
+        ] {
+            g.make_move(m);
+        }
+        assert!(g.is_over());
+        assert!(matches!(
+            g.get_result(),
+            Some(GameResult::Winner(PlayerId::Second))
+        ));
+    }
+}
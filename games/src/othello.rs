@@ -2,7 +2,7 @@ use dfdx::prelude::*;
 use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
 use std::{collections::HashMap, fmt};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum OthelloState {
     Empty,
     White,
@@ -120,6 +120,14 @@ impl Othello {
         }
     }
 
+    pub fn empty_squares(&self) -> usize {
+        self.board
+            .iter()
+            .flatten()
+            .filter(|tile| **tile == OthelloState::Empty)
+            .count()
+    }
+
     pub fn new_from_board(board: [[OthelloState; 8]; 8], playing: PlayerId) -> Othello {
         Othello {
             board,
@@ -228,40 +236,32 @@ impl Game for Othello {
     type TotalBoardSize = Const<64>;
     const CHANNELS: usize = 3;
 
-    fn to_nn_input(
-        &self,
-    ) -> Tensor<(Const<{Self::CHANNELS}>, Self::BoardSizeX, Self::BoardSizeY), f32, AutoDevice>{
-        let dev: AutoDevice = Default::default();
-        let mut black_channel = [[0.0; 8]; 8];
-        for (x, row) in self.board.iter().enumerate() {
-            for (y, tile) in row.iter().enumerate() {
-                black_channel[x][y] = match tile {
-                    OthelloState::Empty => 0.0,
-                    OthelloState::White => 0.0,
-                    OthelloState::Black => 1.0,
-                }
-            }
-        }
+    fn write_nn_input(&self, out: &mut [f32]) {
+        let player_num = match self.playing {
+            PlayerId::First => 1.0_f32,
+            PlayerId::Second => 0.0,
+        };
 
-        let mut white_channel = [[0.0; 8]; 8];
+        // Channels are black's pieces, white's pieces and the player to move
         for (x, row) in self.board.iter().enumerate() {
             for (y, tile) in row.iter().enumerate() {
-                white_channel[x][y] = match tile {
-                    OthelloState::Empty => 0.0,
-                    OthelloState::White => 1.0,
-                    OthelloState::Black => 0.0,
+                let i = x * 8 + y;
+                match tile {
+                    OthelloState::Empty => {}
+                    OthelloState::Black => out[i] = 1.0,
+                    OthelloState::White => out[64 + i] = 1.0,
                 }
+                out[128 + i] = player_num;
             }
         }
+    }
 
-        let player_num = match self.playing {
-            PlayerId::First => 1.0_f32,
-            PlayerId::Second => 0.0,
-        };
-
-        let player_channel = [[player_num; 8]; 8];
-
-        dev.tensor([black_channel, white_channel, player_channel])
+    fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+        self.write_nn_input(out);
+        if player == PlayerId::Second {
+            let (black_channel, rest) = out.split_at_mut(64);
+            black_channel.swap_with_slice(&mut rest[..64]);
+        }
     }
 
     fn get_board(&self) -> Self::Board {
@@ -324,10 +324,38 @@ impl Game for Othello {
 #[cfg(test)]
 mod test {
     use super::Othello;
-    use rust_games_shared::Game;
+    use dfdx::prelude::*;
+    use rust_games_shared::{Game, NnEncoder};
 
     #[test]
     fn empty_board() {
         Othello::new().print()
     }
+
+    #[test]
+    fn canonical_puts_mover_first() {
+        let mut g = Othello::new();
+        assert_eq!(g.to_canonical_nn_input().array(), g.to_nn_input().array());
+
+        let m = g.legal_moves()[0];
+        g.make_move(m);
+        let [black, white, player] = g.to_nn_input().array();
+        assert_eq!(g.to_canonical_nn_input().array(), [white, black, player]);
+    }
+
+    #[test]
+    fn batch_encoding_matches_single() {
+        let dev: AutoDevice = Default::default();
+        let mut encoder: NnEncoder<Othello, AutoDevice> = NnEncoder::new(&dev);
+
+        let start = Othello::new();
+        let mut after = start.clone();
+        after.make_move(start.legal_moves()[0]);
+
+        let batch = encoder.encode_batch([&start, &after], true);
+        assert_eq!(batch.shape().0, 2);
+        assert_eq!(batch.as_vec()[..Othello::nn_input_len()], start.to_canonical_nn_input().as_vec());
+        assert_eq!(batch.as_vec()[Othello::nn_input_len()..], after.to_canonical_nn_input().as_vec());
+        assert_eq!(encoder.encode(&after, false).array(), after.to_nn_input().array());
+    }
 }
@@ -0,0 +1,443 @@
+use core::fmt;
+use std::collections::{HashMap, VecDeque};
+
+use dfdx::prelude::*;
+use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
+
+const SIZE: usize = 9;
+const WALL_SLOTS: usize = SIZE - 1;
+const WALLS_PER_PLAYER: u8 = 10;
+
+/*
+Walls sit on the 8×8 grid of points between squares, each one two squares long.
+A horizontal wall at (x, y) separates rows y and y+1 in columns x and x+1,
+a vertical wall at (x, y) separates columns x and x+1 in rows y and y+1.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuoridorMove {
+    Step(usize, usize),
+    HorizontalWall(usize, usize),
+    VerticalWall(usize, usize),
+}
+
+impl fmt::Display for QuoridorMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let square = |x: usize, y: usize| format!("{}{}", (b'a' + x as u8) as char, y + 1);
+        match *self {
+            QuoridorMove::Step(x, y) => write!(f, "{}", square(x, y)),
+            QuoridorMove::HorizontalWall(x, y) => write!(f, "{}h", square(x, y)),
+            QuoridorMove::VerticalWall(x, y) => write!(f, "{}v", square(x, y)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuoridorBoard {
+    pub pawns: [(usize, usize); 2],
+    pub horizontal_walls: [[bool; WALL_SLOTS]; WALL_SLOTS],
+    pub vertical_walls: [[bool; WALL_SLOTS]; WALL_SLOTS],
+    pub walls_left: [u8; 2],
+}
+
+/*
+Two player Quoridor on a 9×9 board with 10 walls each.
+The first player starts on e1 and races to rank 9, the second starts on e9 and races to rank 1.
+A wall may never cut either player off from their goal, so every wall placement needs a path search.
+ */
+#[derive(Debug, Clone)]
+pub struct Quoridor {
+    board: QuoridorBoard,
+    playing: PlayerId,
+}
+
+fn goal_row(player: PlayerId) -> usize {
+    match player {
+        PlayerId::First => SIZE - 1,
+        PlayerId::Second => 0,
+    }
+}
+
+impl Quoridor {
+    // Whether a wall stands between a square and its neighbour in direction (dx, dy)
+    fn blocked(&self, x: usize, y: usize, dx: isize, dy: isize) -> bool {
+        let h = &self.board.horizontal_walls;
+        let v = &self.board.vertical_walls;
+        match (dx, dy) {
+            (0, 1) | (0, -1) => {
+                let row = if dy == 1 { y } else { y - 1 };
+                (x < WALL_SLOTS && h[row][x]) || (x > 0 && h[row][x - 1])
+            }
+            _ => {
+                let col = if dx == 1 { x } else { x - 1 };
+                (y < WALL_SLOTS && v[y][col]) || (y > 0 && v[y - 1][col])
+            }
+        }
+    }
+
+    // The neighbouring square in direction (dx, dy), if it is on the board and not walled off
+    fn neighbour(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<(usize, usize)> {
+        let (nx, ny) = (x as isize + dx, y as isize + dy);
+        if nx < 0 || ny < 0 || nx >= SIZE as isize || ny >= SIZE as isize {
+            return None;
+        }
+        if self.blocked(x, y, dx, dy) {
+            return None;
+        }
+        Some((nx as usize, ny as usize))
+    }
+
+    fn has_path_to_goal(&self, player: PlayerId) -> bool {
+        self.distance_to_goal(player).is_some()
+    }
+
+    // The fewest steps a player needs to reach their goal row, ignoring the other pawn
+    pub fn distance_to_goal(&self, player: PlayerId) -> Option<usize> {
+        let start = self.board.pawns[usize::from(player)];
+        let goal = goal_row(player);
+
+        let mut seen = [[false; SIZE]; SIZE];
+        let mut queue = VecDeque::from([(start, 0)]);
+        seen[start.1][start.0] = true;
+
+        while let Some(((x, y), steps)) = queue.pop_front() {
+            if y == goal {
+                return Some(steps);
+            }
+            for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+                if let Some((nx, ny)) = self.neighbour(x, y, dx, dy) {
+                    if !seen[ny][nx] {
+                        seen[ny][nx] = true;
+                        queue.push_back(((nx, ny), steps + 1));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn wall_fits(&self, m: QuoridorMove) -> bool {
+        let h = &self.board.horizontal_walls;
+        let v = &self.board.vertical_walls;
+        match m {
+            QuoridorMove::HorizontalWall(x, y) => {
+                !h[y][x]
+                    && !v[y][x]
+                    && (x == 0 || !h[y][x - 1])
+                    && (x + 1 >= WALL_SLOTS || !h[y][x + 1])
+            }
+            QuoridorMove::VerticalWall(x, y) => {
+                !v[y][x]
+                    && !h[y][x]
+                    && (y == 0 || !v[y - 1][x])
+                    && (y + 1 >= WALL_SLOTS || !v[y + 1][x])
+            }
+            QuoridorMove::Step(_, _) => false,
+        }
+    }
+
+    fn place_wall(&mut self, m: QuoridorMove) {
+        match m {
+            QuoridorMove::HorizontalWall(x, y) => self.board.horizontal_walls[y][x] = true,
+            QuoridorMove::VerticalWall(x, y) => self.board.vertical_walls[y][x] = true,
+            QuoridorMove::Step(_, _) => {}
+        }
+    }
+
+    fn pawn_moves(&self) -> Vec<QuoridorMove> {
+        let (x, y) = self.board.pawns[usize::from(self.playing)];
+        let opponent = self.board.pawns[usize::from(self.playing.opponent())];
+
+        let mut moves = vec![];
+        for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+            let Some((nx, ny)) = self.neighbour(x, y, dx, dy) else {
+                continue;
+            };
+            if (nx, ny) != opponent {
+                moves.push(QuoridorMove::Step(nx, ny));
+                continue;
+            }
+
+            // Jump straight over the opponent, or diagonally if something is behind them
+            match self.neighbour(nx, ny, dx, dy) {
+                Some((jx, jy)) => moves.push(QuoridorMove::Step(jx, jy)),
+                None => {
+                    for (sx, sy) in [(dy, dx), (-dy, -dx)] {
+                        if let Some((jx, jy)) = self.neighbour(nx, ny, sx, sy) {
+                            moves.push(QuoridorMove::Step(jx, jy));
+                        }
+                    }
+                }
+            }
+        }
+        moves
+    }
+
+    fn wall_moves(&self) -> Vec<QuoridorMove> {
+        let mut moves = vec![];
+        if self.board.walls_left[usize::from(self.playing)] == 0 {
+            return moves;
+        }
+
+        for y in 0..WALL_SLOTS {
+            for x in 0..WALL_SLOTS {
+                for wall in [
+                    QuoridorMove::HorizontalWall(x, y),
+                    QuoridorMove::VerticalWall(x, y),
+                ] {
+                    if !self.wall_fits(wall) {
+                        continue;
+                    }
+                    let mut after = self.clone();
+                    after.place_wall(wall);
+                    if after.has_path_to_goal(PlayerId::First)
+                        && after.has_path_to_goal(PlayerId::Second)
+                    {
+                        moves.push(wall);
+                    }
+                }
+            }
+        }
+        moves
+    }
+}
+
+impl Game for Quoridor {
+    type Move = QuoridorMove;
+
+    type Board = QuoridorBoard;
+
+    type BoardSizeX = Const<9>;
+    type BoardSizeY = Const<9>;
+
+    type TotalBoardSize = Const<81>;
+
+    const CHANNELS: usize = 2 /* Pawns */ + 2 /* Walls */ + 2 /* Walls left */ + 1 /* Player */;
+
+    const NUM_PLAYERS: usize = 2;
+
+    const TOTAL_MOVES: usize = 81 /* Pawn steps */ + 2 * 64 /* Walls */;
+
+    fn new() -> Self {
+        Self {
+            board: QuoridorBoard {
+                pawns: [(4, 0), (4, SIZE - 1)],
+                horizontal_walls: [[false; WALL_SLOTS]; WALL_SLOTS],
+                vertical_walls: [[false; WALL_SLOTS]; WALL_SLOTS],
+                walls_left: [WALLS_PER_PLAYER; 2],
+            },
+            playing: PlayerId::First,
+        }
+    }
+
+    fn print(&self) {
+        for y in (0..SIZE).rev() {
+            print!("{} ", y + 1);
+            for x in 0..SIZE {
+                let icon = if self.board.pawns[0] == (x, y) {
+                    "1"
+                } else if self.board.pawns[1] == (x, y) {
+                    "2"
+                } else {
+                    "·"
+                };
+                print!("{}", icon);
+                if x + 1 < SIZE {
+                    print!("{}", if self.blocked(x, y, 1, 0) { "┃" } else { " " });
+                }
+            }
+            println!();
+            if y > 0 {
+                print!("  ");
+                for x in 0..SIZE {
+                    print!("{} ", if self.blocked(x, y, 0, -1) { "━" } else { " " });
+                }
+                println!();
+            }
+        }
+        println!("  a b c d e f g h i");
+        println!(
+            "Walls left: {} - {}",
+            self.board.walls_left[0], self.board.walls_left[1]
+        );
+        println!("Currently playing: {:?}", self.playing);
+    }
+
+    fn write_nn_input(&self, out: &mut [f32]) {
+        let mut pawn_channels = [[[0.0_f32; SIZE]; SIZE]; 2];
+        for (channel, (x, y)) in pawn_channels.iter_mut().zip(self.board.pawns) {
+            channel[y][x] = 1.0;
+        }
+
+        // Each wall is marked on the square at its lower left corner
+        let mut horizontal_channel = [[0.0_f32; SIZE]; SIZE];
+        let mut vertical_channel = [[0.0_f32; SIZE]; SIZE];
+        for y in 0..WALL_SLOTS {
+            for x in 0..WALL_SLOTS {
+                if self.board.horizontal_walls[y][x] {
+                    horizontal_channel[y][x] = 1.0;
+                }
+                if self.board.vertical_walls[y][x] {
+                    vertical_channel[y][x] = 1.0;
+                }
+            }
+        }
+
+        let walls_left = |player: usize| {
+            [[self.board.walls_left[player] as f32 / WALLS_PER_PLAYER as f32; SIZE]; SIZE]
+        };
+
+        let player_num = match self.playing {
+            PlayerId::First => 1.0_f32,
+            PlayerId::Second => 0.0,
+        };
+
+        let channels = [
+            pawn_channels[0],
+            pawn_channels[1],
+            horizontal_channel,
+            vertical_channel,
+            walls_left(0),
+            walls_left(1),
+            [[player_num; SIZE]; SIZE],
+        ];
+        for (rows_out, channel) in out.chunks_mut(SIZE * SIZE).zip(channels.iter()) {
+            for (row_out, row) in rows_out.chunks_mut(SIZE).zip(channel.iter()) {
+                row_out.copy_from_slice(row);
+            }
+        }
+    }
+
+    // The player's pawn and walls left come first, each followed by the opponent's
+    fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+        self.write_nn_input(out);
+        if player == PlayerId::Second {
+            let plane = SIZE * SIZE;
+            for first in [0, 4] {
+                let (first_channel, rest) = out[first * plane..].split_at_mut(plane);
+                first_channel.swap_with_slice(&mut rest[..plane]);
+            }
+        }
+    }
+
+    fn get_board(&self) -> Self::Board {
+        self.board.clone()
+    }
+
+    fn legal_moves(&self) -> Vec<Self::Move> {
+        if self.is_over() {
+            return vec![];
+        }
+
+        let mut moves = self.pawn_moves();
+        moves.extend(self.wall_moves());
+        moves
+    }
+
+    fn make_move(&mut self, m: Self::Move) {
+        let p = usize::from(self.playing);
+        match m {
+            QuoridorMove::Step(x, y) => self.board.pawns[p] = (x, y),
+            wall => {
+                self.place_wall(wall);
+                self.board.walls_left[p] -= 1;
+            }
+        }
+
+        self.playing = self.playing.opponent();
+    }
+
+    fn is_over(&self) -> bool {
+        self.get_result().is_some()
+    }
+
+    fn get_result(&self) -> Option<GameResult> {
+        for player in [PlayerId::First, PlayerId::Second] {
+            if self.board.pawns[usize::from(player)].1 == goal_row(player) {
+                return Some(GameResult::Winner(player));
+            }
+        }
+        None
+    }
+
+    fn current_player(&self) -> PlayerId {
+        self.playing
+    }
+
+    fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES] {
+        let mut moves = [QuoridorMove::Step(0, 0); Self::TOTAL_MOVES];
+
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                moves[y * SIZE + x] = QuoridorMove::Step(x, y);
+            }
+        }
+        for y in 0..WALL_SLOTS {
+            for x in 0..WALL_SLOTS {
+                let i = SIZE * SIZE + 2 * (y * WALL_SLOTS + x);
+                moves[i] = QuoridorMove::HorizontalWall(x, y);
+                moves[i + 1] = QuoridorMove::VerticalWall(x, y);
+            }
+        }
+
+        moves
+    }
+
+    fn associate_players(
+        players: Vec<&Strategy<Self>>,
+    ) -> std::collections::HashMap<PlayerId, &Strategy<Self>> {
+        let mut players_map = HashMap::new();
+        players_map.insert(PlayerId::First, players[0]);
+        players_map.insert(PlayerId::Second, players[1]);
+
+        players_map
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Quoridor, QuoridorMove};
+    use rust_games_shared::Game;
+
+    #[test]
+    fn opening_moves() {
+        let g = Quoridor::new();
+        // Three pawn steps and every one of the 128 walls
+        assert_eq!(g.legal_moves().len(), 3 + 128);
+    }
+
+    #[test]
+    fn walls_cannot_cross_or_overlap() {
+        let mut g = Quoridor::new();
+        g.make_move(QuoridorMove::HorizontalWall(3, 3));
+        let moves = g.legal_moves();
+        assert!(!moves.contains(&QuoridorMove::VerticalWall(3, 3)));
+        assert!(!moves.contains(&QuoridorMove::HorizontalWall(4, 3)));
+        assert!(moves.contains(&QuoridorMove::HorizontalWall(5, 3)));
+    }
+
+    #[test]
+    fn jump_over_opponent() {
+        let mut g = Quoridor::new();
+        g.board.pawns = [(4, 3), (4, 4)];
+        assert!(g.legal_moves().contains(&QuoridorMove::Step(4, 5)));
+
+        // With a wall behind the opponent, jump diagonally instead
+        g.board.horizontal_walls[4][4] = true;
+        let moves = g.legal_moves();
+        assert!(!moves.contains(&QuoridorMove::Step(4, 5)));
+        assert!(moves.contains(&QuoridorMove::Step(3, 4)));
+        assert!(moves.contains(&QuoridorMove::Step(5, 4)));
+    }
+
+    #[test]
+    fn walls_must_leave_a_path() {
+        let mut g = Quoridor::new();
+        // Box the first player's pawn in against the bottom edge, leaving one gap
+        g.board.horizontal_walls[0][2] = true;
+        g.board.horizontal_walls[0][4] = true;
+        g.board.vertical_walls[0][1] = true;
+        assert!(!g
+            .legal_moves()
+            .contains(&QuoridorMove::VerticalWall(5, 0)));
+    }
+}
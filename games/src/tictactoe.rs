@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use dfdx::prelude::*;
 use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum TTTState {
     Empty,
     X,
@@ -94,41 +94,32 @@ impl Game for TicTacToe {
         println!("Currently playing: {}", to_state(&self.playing));
     }
 
-    fn to_nn_input(
-        &self,
-    ) -> Tensor<(Const<{ Self::CHANNELS }>, Self::BoardSizeX, Self::BoardSizeY), f32, AutoDevice>
-    {
-        let dev: AutoDevice = Default::default();
-        let mut o_channel = [[0.0; 3]; 3];
-        for (x, row) in self.board.iter().enumerate() {
-            for (y, tile) in row.iter().enumerate() {
-                o_channel[x][y] = match tile {
-                    TTTState::Empty => 0.0,
-                    TTTState::X => 0.0,
-                    TTTState::O => 1.0,
-                }
-            }
-        }
+    fn write_nn_input(&self, out: &mut [f32]) {
+        let player_num = match self.playing {
+            PlayerId::First => 1.0_f32,
+            PlayerId::Second => 0.0,
+        };
 
-        let mut x_channel = [[0.0; 3]; 3];
+        // Channels are X's pieces, O's pieces and the player to move
         for (x, row) in self.board.iter().enumerate() {
             for (y, tile) in row.iter().enumerate() {
-                x_channel[x][y] = match tile {
-                    TTTState::Empty => 0.0,
-                    TTTState::X => 1.0,
-                    TTTState::O => 0.0,
+                let i = x * 3 + y;
+                match tile {
+                    TTTState::Empty => {}
+                    TTTState::X => out[i] = 1.0,
+                    TTTState::O => out[9 + i] = 1.0,
                 }
+                out[18 + i] = player_num;
             }
         }
+    }
 
-        let player_num = match self.playing {
-            PlayerId::First => 1.0_f32,
-            PlayerId::Second => 0.0,
-        };
-
-        let player_channel = [[player_num; 3]; 3];
-
-        dev.tensor([x_channel, o_channel, player_channel])
+    fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+        self.write_nn_input(out);
+        if player == PlayerId::Second {
+            let (x_channel, rest) = out.split_at_mut(9);
+            x_channel.swap_with_slice(&mut rest[..9]);
+        }
     }
 
     fn get_board(&self) -> Self::Board {
@@ -164,24 +155,29 @@ impl Game for TicTacToe {
     }
 
     fn get_result(&self) -> Option<GameResult> {
-        // Horizontal
-        for d in 0..3 {
-            if self.board[d][0] == self.board[d][1] && self.board[d][0] == self.board[d][2] {
-                return Some(GameResult::Winner(self.board[d][0].to_player().unwrap()));
-            }
-        }
-        // Vertical
+        let mut lines = vec![];
         for d in 0..3 {
-            if self.board[0][d] == self.board[1][d] && self.board[0][d] == self.board[2][d] {
-                return Some(GameResult::Winner(self.board[0][d].to_player().unwrap()));
-            }
+            // Horizontal
+            lines.push([self.board[d][0], self.board[d][1], self.board[d][2]]);
+            // Vertical
+            lines.push([self.board[0][d], self.board[1][d], self.board[2][d]]);
         }
         // Diagonal
-        if self.board[0][0] == self.board[1][1] && self.board[0][0] == self.board[2][2] {
-            return Some(GameResult::Winner(self.board[1][1].to_player().unwrap()));
+        lines.push([self.board[0][0], self.board[1][1], self.board[2][2]]);
+        lines.push([self.board[0][2], self.board[1][1], self.board[2][0]]);
+
+        // A line of empty squares has no owner, so it doesn't win
+        for line in lines {
+            if line[0] == line[1] && line[0] == line[2] {
+                if let Some(winner) = line[0].to_player() {
+                    return Some(GameResult::Winner(winner));
+                }
+            }
         }
-        if self.board[0][2] == self.board[1][1] && self.board[0][2] == self.board[2][0] {
-            return Some(GameResult::Winner(self.board[1][1].to_player().unwrap()));
+
+        // A full board with no three in a row is a tie
+        if self.legal_moves().is_empty() {
+            return Some(GameResult::Tie);
         }
 
         None
@@ -213,3 +209,32 @@ impl Game for TicTacToe {
         players_map
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::TicTacToe;
+    use rust_games_shared::{Game, GameResult, PlayerId};
+
+    fn play(indices: &[usize]) -> TicTacToe {
+        let mut g = TicTacToe::new();
+        let moves = TicTacToe::all_possible_moves();
+        for i in indices {
+            g.make_move(moves[*i]);
+        }
+        g
+    }
+
+    #[test]
+    fn empty_lines_dont_win() {
+        assert!(TicTacToe::new().get_result().is_none());
+        // Only X's anti-diagonal is complete, with empty rows and columns around it
+        assert!(matches!(
+            play(&[6, 0, 4, 1, 2]).get_result(),
+            Some(GameResult::Winner(PlayerId::First))
+        ));
+        assert!(matches!(
+            play(&[0, 1, 2, 4, 3, 5, 7, 6, 8]).get_result(),
+            Some(GameResult::Tie)
+        ));
+    }
+}
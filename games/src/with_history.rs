@@ -0,0 +1,208 @@
+use std::collections::{HashMap, VecDeque};
+
+use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
+
+use crate::{
+    Amazons, Backgammon, BlokusDuo, Breakthrough, Connect4, DotsAndBoxes, Othello, Quoridor, TicTacToe,
+};
+
+/*
+Feeds the network the last K positions instead of only the current one, for repetition rules and tempo.
+The current position's channels come first, then each earlier position's, with zeros before the game started.
+Every state keeps its own history, so MCTS nodes and the training examples built from them have all they need.
+ */
+#[derive(Debug, Clone)]
+pub struct WithHistory<G: Game, const K: usize> {
+    game: G,
+    // Earlier positions, most recent first, at most K - 1 of them
+    history: VecDeque<G>,
+}
+
+impl<G: Game, const K: usize> WithHistory<G, K> {
+    pub fn new_from_game(game: G) -> Self {
+        assert!(K >= 1, "The history must include the current position");
+        Self {
+            game,
+            history: VecDeque::new(),
+        }
+    }
+
+    pub fn inner(&self) -> &G {
+        &self.game
+    }
+
+    pub fn history(&self) -> impl Iterator<Item = &G> {
+        self.history.iter()
+    }
+
+    // Writes the planes of the current and earlier positions one after another, leaving missing ones zeroed
+    fn write_planes(&self, out: &mut [f32], write: impl Fn(&G, &mut [f32])) {
+        let positions = std::iter::once(&self.game).chain(self.history.iter());
+        for (planes, game) in out.chunks_mut(G::nn_input_len()).zip(positions) {
+            write(game, planes);
+        }
+    }
+
+    fn boards(&self) -> Vec<G::Board> {
+        std::iter::once(&self.game)
+            .chain(self.history.iter())
+            .map(|game| game.get_board())
+            .collect()
+    }
+
+    fn play(&mut self, m: G::Move) {
+        if K > 1 {
+            self.history.push_front(self.game.clone());
+            self.history.truncate(K - 1);
+        }
+        self.game.make_move(m);
+    }
+}
+
+// The wrapped game is spelled out in each impl, see for_each_wrapped_game
+macro_rules! impl_with_history {
+    ([] $($game:ty),* $(,)?) => {
+        $(
+            impl<const K: usize> Game for WithHistory<$game, K> {
+                type Move = <$game as Game>::Move;
+
+                // Repetitions depend on the earlier positions, so they are part of the state
+                type Board = Vec<<$game as Game>::Board>;
+
+                type BoardSizeX = <$game as Game>::BoardSizeX;
+                type BoardSizeY = <$game as Game>::BoardSizeY;
+
+                type TotalBoardSize = <$game as Game>::TotalBoardSize;
+
+                const CHANNELS: usize = <$game as Game>::CHANNELS * K;
+
+                const NUM_PLAYERS: usize = <$game as Game>::NUM_PLAYERS;
+
+                const PERFECT_INFORMATION: bool = <$game as Game>::PERFECT_INFORMATION;
+
+                const TOTAL_MOVES: usize = <$game as Game>::TOTAL_MOVES;
+
+                fn new() -> Self {
+                    Self::new_from_game(<$game>::new())
+                }
+
+                fn print(&self) {
+                    self.game.print();
+                }
+
+                fn write_nn_input(&self, out: &mut [f32]) {
+                    self.write_planes(out, |game, out| game.write_nn_input(out));
+                }
+
+                // Every earlier position is seen from the same player too, not from whoever was to move then
+                fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]) {
+                    self.write_planes(out, |game, out| game.write_perspective_nn_input(player, out));
+                }
+
+                fn get_board(&self) -> Self::Board {
+                    self.boards()
+                }
+
+                fn legal_moves(&self) -> Vec<Self::Move> {
+                    self.game.legal_moves()
+                }
+
+                fn make_move(&mut self, m: Self::Move) {
+                    self.play(m)
+                }
+
+                fn is_over(&self) -> bool {
+                    self.game.is_over()
+                }
+
+                fn get_result(&self) -> Option<GameResult> {
+                    self.game.get_result()
+                }
+
+                fn score_margin(&self) -> Option<f32> {
+                    self.game.score_margin()
+                }
+
+                fn current_player(&self) -> PlayerId {
+                    self.game.current_player()
+                }
+
+                fn is_chance_node(&self) -> bool {
+                    self.game.is_chance_node()
+                }
+
+                fn chance_outcomes(&self) -> Vec<(Self::Move, f32)> {
+                    self.game.chance_outcomes()
+                }
+
+                fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES] {
+                    <$game>::all_possible_moves()
+                }
+
+                fn associate_players(
+                    players: Vec<&Strategy<Self>>,
+                ) -> std::collections::HashMap<PlayerId, &Strategy<Self>> {
+                    let mut players_map = HashMap::new();
+                    players_map.insert(PlayerId::First, players[0]);
+                    players_map.insert(PlayerId::Second, players[1]);
+
+                    players_map
+                }
+            }
+        )*
+    };
+}
+
+for_each_wrapped_game!(impl_with_history);
+
+#[cfg(test)]
+mod test {
+    use super::WithHistory;
+    use crate::Connect4;
+    use rust_games_shared::{Game, PlayerId};
+
+    #[test]
+    fn earlier_positions_follow_the_current_one() {
+        let mut g: WithHistory<Connect4, 3> = WithHistory::new();
+        let start = Connect4::new().to_nn_input().as_vec();
+        let plane_size = start.len();
+
+        let input = g.to_nn_input().as_vec();
+        assert_eq!(input.len(), 3 * plane_size);
+        assert!(input[plane_size..].iter().all(|x| *x == 0.0));
+
+        for _ in 0..3 {
+            let m = g.legal_moves()[0];
+            g.make_move(m);
+        }
+        assert_eq!(g.history().count(), 2);
+        assert_eq!(g.get_board().len(), 3);
+
+        let input = g.to_nn_input().as_vec();
+        assert_eq!(&input[..plane_size], &g.inner().to_nn_input().as_vec()[..]);
+        assert_ne!(&input[2 * plane_size..], &start[..]);
+    }
+
+    #[test]
+    fn canonical_history_is_seen_by_the_mover() {
+        let mut g: WithHistory<Connect4, 2> = WithHistory::new();
+        for _ in 0..3 {
+            let m = g.legal_moves()[0];
+            g.make_move(m);
+        }
+        assert_eq!(g.current_player(), PlayerId::Second);
+
+        // The previous position had First to move, but is shown from Second's side as well
+        let plane_size = Connect4::nn_input_len();
+        let previous = g.history().next().unwrap();
+        let mut seen_by_second = vec![0.0; plane_size];
+        let mut seen_by_first = vec![0.0; plane_size];
+        previous.write_perspective_nn_input(PlayerId::Second, &mut seen_by_second);
+        previous.write_canonical_nn_input(&mut seen_by_first);
+
+        let input = g.to_canonical_nn_input().as_vec();
+        assert_eq!(&input[..plane_size], &g.inner().to_canonical_nn_input().as_vec()[..]);
+        assert_eq!(&input[plane_size..], &seen_by_second[..]);
+        assert_ne!(&input[plane_size..], &seen_by_first[..]);
+    }
+}
@@ -0,0 +1,32 @@
+use rust_games_shared::{Elo, Game, Strategy};
+
+use crate::Leaderboard;
+
+/*
+Searches for the handicap that rates a bot at the target Elo against the leaderboard, by bisection over
+[low, high]. The handicap must weaken the bot as it grows, like EpsilonRandom's epsilon or TopK's k,
+so make builds the handicapped bot for a value, rounding it if the parameter is a whole number.
+Each step rates a fresh bot over the given number of games. Returns the value found after the last step,
+along with every value tried and the rating it got, for the caller to report.
+ */
+pub fn calibrate<G: Game>(
+    leaderboard: &mut Leaderboard<G>,
+    make: impl Fn(f64) -> Strategy<G>,
+    (mut low, mut high): (f64, f64),
+    target_elo: f64,
+    games: usize,
+    steps: usize,
+) -> (f64, Vec<(f64, Elo)>) {
+    let mut trace = vec![];
+    for _ in 0..steps {
+        let middle = (low + high) / 2.0;
+        let elo = leaderboard.rate(make(middle), games);
+        if elo.rating > target_elo {
+            low = middle;
+        } else {
+            high = middle;
+        }
+        trace.push((middle, elo));
+    }
+    ((low + high) / 2.0, trace)
+}
@@ -1,7 +1,10 @@
 use std::{collections::HashMap, rc::Rc};
 
 use indicatif::{ProgressBar, ProgressStyle};
-use rand::{rngs::ThreadRng, seq::IteratorRandom};
+use rand::{
+    rngs::ThreadRng,
+    seq::{IteratorRandom, SliceRandom},
+};
 use rust_games_shared::{Elo, Game, GameResult, Player, Strategy};
 pub struct Leaderboard<G: Game> {
     pub strategies: HashMap<usize, Strategy<G>>,
@@ -61,6 +64,10 @@ impl<'a, G: Game> Leaderboard<G> {
             .map(|k| **k)
             .collect();
 
+        self.play_game(player_indices, verbose);
+    }
+
+    fn play_game(&mut self, player_indices: Vec<usize>, verbose: bool) {
         let players: Vec<Strategy<G>> = player_indices
             .iter()
             .map(|key| {
@@ -96,6 +103,38 @@ impl<'a, G: Game> Leaderboard<G> {
         progress_bar.finish_and_clear();
     }
 
+    /*
+    Rates a newcomer by playing it n games against randomly chosen members of the board, in random seats.
+    The members' ratings are kept as they were, so they anchor the newcomer's, and it leaves the board afterwards.
+     */
+    pub fn rate(&mut self, strategy: Strategy<G>, n: usize) -> Elo {
+        let anchors: HashMap<usize, Elo> = self
+            .strategies
+            .iter()
+            .map(|(key, strat)| (*key, strat.elo))
+            .collect();
+        let newcomer = self.strategies.keys().max().map_or(0, |key| key + 1);
+        self.strategies.insert(newcomer, strategy);
+
+        for _ in 0..n {
+            let mut player_indices: Vec<usize> = anchors
+                .keys()
+                .choose_multiple(&mut self.rng, G::NUM_PLAYERS - 1)
+                .into_iter()
+                .copied()
+                .collect();
+            player_indices.push(newcomer);
+            player_indices.shuffle(&mut self.rng);
+            self.play_game(player_indices, false);
+
+            for (key, elo) in anchors.iter() {
+                self.strategies.get_mut(key).unwrap().elo = *elo;
+            }
+        }
+
+        self.strategies.remove(&newcomer).unwrap().elo
+    }
+
     pub fn print(&self) {
         for strat_id in self.strategies.keys() {
             let strat = self.strategies.get(&strat_id).unwrap();
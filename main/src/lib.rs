@@ -1,3 +1,5 @@
+mod calibration;
 mod leaderboard;
 
+pub use calibration::calibrate;
 pub use leaderboard::Leaderboard;
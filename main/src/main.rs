@@ -4,8 +4,8 @@
 use alphazero::{AlphaZeroPlayer, BoardGameModel};
 use dfdx::prelude::*;
 use rust_games_games::{Othello, TicTacToe};
-use rust_games_main::Leaderboard;
-use rust_games_players::{Corners, Greedy, Random};
+use rust_games_main::{calibrate, Leaderboard};
+use rust_games_players::{Corners, Ensemble, EpsilonRandom, Greedy, Random, Vote};
 use rust_games_shared::Strategy;
 
 fn main() {
@@ -30,6 +30,20 @@ fn main() {
         AlphaZeroPlayer::new_from_file::<BoardGameModel<G>>("best", data_dir, 1.0, &dev, false, AZ_DEPTH),
     );
 
+    // Both checkpoints searching together, to see whether the committee beats the best one alone
+    let az_committee = Strategy::new(
+        "AlphaZero Committee".to_string(),
+        Ensemble::new(Vote::PolicyAverage)
+            .with_member(
+                AlphaZeroPlayer::new_from_file::<BoardGameModel<G>>("control", data_dir, 1.0, &dev, false, AZ_DEPTH),
+                1.0,
+            )
+            .with_member(
+                AlphaZeroPlayer::new_from_file::<BoardGameModel<G>>("best", data_dir, 1.0, &dev, false, AZ_DEPTH),
+                2.0,
+            ),
+    );
+
     let players = vec![
         corner_player,
         rand_player_1,
@@ -37,10 +51,33 @@ fn main() {
         greedy_player,
         dumb_az_player,
         az_player_best,
+        az_committee,
     ];
 
     let mut arena = Leaderboard::new(players);
     arena.print();
     arena.play_random_games(500);
     arena.print();
+
+    // How often the best checkpoint has to play at random to be a fair game for a 1400 player
+    let (epsilon, trace) = calibrate(
+        &mut arena,
+        |epsilon| {
+            Strategy::new(
+                format!("AlphaZero Best, epsilon {:.3}", epsilon),
+                EpsilonRandom::new(
+                    AlphaZeroPlayer::new_from_file::<BoardGameModel<G>>("best", data_dir, 1.0, &dev, false, AZ_DEPTH),
+                    epsilon as f32,
+                ),
+            )
+        },
+        (0.0, 1.0),
+        1400.0,
+        100,
+        6,
+    );
+    for (tried, elo) in trace {
+        println!("Epsilon {:.3} rates {:.0}", tried, elo.rating);
+    }
+    println!("Epsilon {:.3} plays at about 1400", epsilon);
 }
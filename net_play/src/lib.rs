@@ -0,0 +1,319 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+/*
+Play between processes over TCP. A referee accepts one connection per player, asks whoever is to move for a move,
+checks it against the Game rules and records the game. Each message is one line of JSON.
+A player whose move is illegal, or who leaves or stalls, forfeits.
+ */
+use rust_games_players::move_notation;
+use rust_games_shared::{Game, GameResult, Player, PlayerId};
+use serde::{Deserialize, Serialize};
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Message {
+    // Client to referee, on connecting
+    Hello { name: String },
+    // Referee to client: which player it is, counting from 0 in turn order
+    Seat { seat: usize },
+    // Referee to client: the moves so far, and a request for the next one
+    YourMove { moves: Vec<String> },
+    // Client to referee
+    Move { notation: String },
+    // Referee to client
+    GameOver { record: GameRecord },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    // The names the players gave, in turn order
+    pub players: Vec<String>,
+    pub moves: Vec<String>,
+    // e.g. "Winner(First)" or "Tie"
+    pub result: String,
+    // Why the game ended early, if it did
+    pub forfeit: Option<String>,
+}
+
+impl GameRecord {
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        serde_json::to_writer_pretty(File::create(path)?, self).map_err(io::Error::from)
+    }
+}
+
+pub struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    pub fn new(stream: TcpStream) -> io::Result<Connection> {
+        Ok(Connection {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    pub fn send(&mut self, message: &Message) -> io::Result<()> {
+        let line = serde_json::to_string(message).map_err(io::Error::from)?;
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()
+    }
+
+    pub fn receive(&mut self) -> io::Result<Message> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "The other side disconnected",
+            ));
+        }
+        serde_json::from_str(&line).map_err(io::Error::from)
+    }
+}
+
+fn player_id(seat: usize) -> PlayerId {
+    match seat {
+        0 => PlayerId::First,
+        _ => PlayerId::Second,
+    }
+}
+
+/*
+Asks a player for their move, returning the notation they sent, or why they forfeit if they
+disconnect, run out of time or send anything other than a move.
+ */
+fn request_move(
+    connection: &mut Connection,
+    name: &str,
+    moves: &[String],
+) -> Result<String, String> {
+    connection
+        .send(&Message::YourMove {
+            moves: moves.to_vec(),
+        })
+        .map_err(|err| format!("{} can't be reached: {}", name, err))?;
+    match connection.receive() {
+        Ok(Message::Move { notation }) => Ok(notation),
+        Ok(other) => Err(format!("{} sent {:?} instead of a move", name, other)),
+        Err(err) => Err(format!("{} didn't send a move: {}", name, err)),
+    }
+}
+
+/*
+Waits for both players to connect to the listener, seating them in the order they arrive, then referees one game.
+Each player has move_timeout to answer. One who doesn't, disconnects or sends something that isn't a legal move
+forfeits, and connections that don't say Hello in time are dropped before the game starts.
+Every move is sent to both players, so only games without hidden information can be refereed.
+ */
+pub fn referee<G: Game>(listener: &TcpListener, move_timeout: Duration) -> io::Result<GameRecord> {
+    if !G::PERFECT_INFORMATION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "The referee would show each player the other's hidden information",
+        ));
+    }
+
+    let mut connections = vec![];
+    let mut players = vec![];
+    while connections.len() < G::NUM_PLAYERS {
+        let (stream, _) = listener.accept()?;
+        stream.set_read_timeout(Some(move_timeout))?;
+        let mut connection = Connection::new(stream)?;
+        let Ok(Message::Hello { name }) = connection.receive() else {
+            continue;
+        };
+        if connection
+            .send(&Message::Seat {
+                seat: connections.len(),
+            })
+            .is_ok()
+        {
+            players.push(name);
+            connections.push(connection);
+        }
+    }
+
+    let mut game = G::new();
+    let mut moves = vec![];
+    let mut forfeit = None;
+    while !game.is_over() {
+        if game.is_chance_node() {
+            let outcome = game.sample_chance();
+            moves.push(move_notation(&outcome));
+            game.make_move(outcome);
+            continue;
+        }
+
+        let mover = usize::from(game.current_player());
+        let name = &players[mover];
+        let notation = match request_move(&mut connections[mover], name, &moves) {
+            Ok(notation) => notation,
+            Err(reason) => {
+                forfeit = Some(reason);
+                break;
+            }
+        };
+
+        let legal = game
+            .legal_moves()
+            .into_iter()
+            .find(|mv| move_notation(mv) == notation);
+        match legal {
+            Some(mv) => {
+                moves.push(notation);
+                game.make_move(mv);
+            }
+            None => {
+                forfeit = Some(format!("{} played {}, which isn't legal", name, notation));
+                break;
+            }
+        }
+    }
+
+    let result = match forfeit {
+        Some(_) => GameResult::Winner(game.current_player().opponent()),
+        None => game.get_result().expect("Game should have finished!"),
+    };
+    let record = GameRecord {
+        players,
+        moves,
+        result: format!("{:?}", result),
+        forfeit,
+    };
+    // A player who forfeited by leaving can't be told
+    for connection in connections.iter_mut() {
+        let _ = connection.send(&Message::GameOver {
+            record: record.clone(),
+        });
+    }
+    Ok(record)
+}
+
+/*
+Joins a referee's game as the given player, replaying the moves it sends to know the position, until the game ends.
+When verbose, each position is printed before moving, for human players.
+ */
+pub fn play_remote<G: Game>(
+    address: impl ToSocketAddrs,
+    name: &str,
+    player: &dyn Player<G>,
+    verbose: bool,
+) -> io::Result<GameRecord> {
+    let mut connection = Connection::new(TcpStream::connect(address)?)?;
+    connection.send(&Message::Hello {
+        name: name.to_string(),
+    })?;
+
+    loop {
+        match connection.receive()? {
+            Message::Seat { seat } => println!("Playing as {:?}", player_id(seat)),
+            Message::YourMove { moves } => {
+                let mut game = G::new();
+                for notation in moves {
+                    let mv = game
+                        .legal_moves()
+                        .into_iter()
+                        .find(|mv| move_notation(mv) == notation)
+                        .ok_or_else(|| {
+                            let reason = format!("Unknown move {}", notation);
+                            io::Error::new(io::ErrorKind::InvalidData, reason)
+                        })?;
+                    game.make_move(mv);
+                }
+                if verbose {
+                    game.print();
+                }
+
+                let mv = player
+                    .choose_move(&game)
+                    .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{:?}", err)))?;
+                connection.send(&Message::Move {
+                    notation: move_notation(&mv),
+                })?;
+            }
+            Message::GameOver { record } => return Ok(record),
+            other => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Unexpected message {:?}", other),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{play_remote, referee, Connection, Message};
+    use rust_games_games::{KuhnPoker, TicTacToe};
+    use rust_games_players::Random;
+    use std::{
+        net::{TcpListener, TcpStream},
+        thread,
+        time::Duration,
+    };
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn two_clients_play_a_game() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let clients: Vec<_> = ["Alice", "Bob"]
+            .into_iter()
+            .map(|name| {
+                thread::spawn(move || {
+                    play_remote::<TicTacToe>(address, name, &Random::new(), false).unwrap()
+                })
+            })
+            .collect();
+
+        let record = referee::<TicTacToe>(&listener, TIMEOUT).unwrap();
+        assert!(record.moves.len() >= 5);
+        assert!(record.forfeit.is_none());
+        for client in clients {
+            assert_eq!(client.join().unwrap().moves, record.moves);
+        }
+    }
+
+    #[test]
+    fn leaving_forfeits() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        // Takes the first seat, then hangs up before moving
+        let mut quitter = Connection::new(TcpStream::connect(address).unwrap()).unwrap();
+        quitter
+            .send(&Message::Hello {
+                name: "Quitter".to_string(),
+            })
+            .unwrap();
+        let quitter = thread::spawn(move || quitter.receive().unwrap());
+        let stayer = thread::spawn(move || {
+            play_remote::<TicTacToe>(address, "Stayer", &Random::new(), false).unwrap()
+        });
+
+        let record = referee::<TicTacToe>(&listener, TIMEOUT).unwrap();
+        assert!(matches!(quitter.join().unwrap(), Message::Seat { seat: 0 }));
+        assert!(record.moves.is_empty());
+        assert!(record.forfeit.unwrap().starts_with("Quitter"));
+        assert_eq!(record.result, "Winner(Second)");
+        assert_eq!(stayer.join().unwrap().result, "Winner(Second)");
+    }
+
+    #[test]
+    fn hidden_information_is_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        assert!(referee::<KuhnPoker>(&listener, TIMEOUT).is_err());
+    }
+}
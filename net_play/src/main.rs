@@ -0,0 +1,145 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use clap::{Parser, ValueEnum};
+use net_play::{play_remote, referee, GameRecord};
+use rust_games_games::{Connect4, Othello, TicTacToe};
+use rust_games_players::{NamedPlayers, PlayerOptions};
+use rust_games_shared::Game;
+
+use std::{net::TcpListener, time::Duration};
+
+// The games implementing NamedPlayers, which are the ones players can join
+#[derive(Clone, Copy, ValueEnum)]
+enum GameName {
+    #[value(name = "tictactoe")]
+    TicTacToe,
+    Connect4,
+    Othello,
+}
+
+#[derive(Parser)]
+enum NetArgs {
+    // Referee one game between the first two players to connect
+    Serve {
+        #[arg(short, long, value_enum)]
+        game: GameName,
+
+        #[arg(short, long, default_value = "127.0.0.1:7878")]
+        address: String,
+
+        // A file to save the game record to, as JSON
+        #[arg(short, long)]
+        record: Option<String>,
+
+        // Seconds a player has for each move before forfeiting
+        #[arg(long, default_value_t = 600)]
+        timeout: u64,
+    },
+    // Play in a refereed game
+    Join {
+        #[arg(short, long, value_enum)]
+        game: GameName,
+
+        #[arg(short, long, default_value = "127.0.0.1:7878")]
+        address: String,
+
+        #[arg(short, long)]
+        name: String,
+
+        // human, random, greedy, alphabeta, uct, alphazero, or one written for the game: solver for tictactoe,
+        // solver or threats for connect4, eval or solver for othello
+        #[arg(short, long, default_value = "human")]
+        player: String,
+
+        #[arg(long, default_value_t = 4)]
+        depth: usize,
+
+        #[arg(long, default_value_t = 200)]
+        iterations: usize,
+
+        #[arg(long, default_value_t = 1_000_000)]
+        nodes: usize,
+
+        #[arg(short, long)]
+        data_dir: Option<String>,
+
+        #[arg(short, long, default_value = "best")]
+        model: String,
+
+        // Whether the alphazero model was trained on positions seen from the player to move
+        #[arg(long)]
+        canonical: bool,
+    },
+}
+
+fn join<G: NamedPlayers>(address: &str, name: &str, player: &str, options: &PlayerOptions) {
+    let bot = G::named_player(player, options).unwrap_or_else(|reason| panic!("{}", reason));
+    let record = play_remote(address, name, bot.as_ref(), player == "human").unwrap();
+    print_record(&record);
+}
+
+fn serve<G: Game>(address: &str, record_file: Option<String>, timeout: u64) {
+    let listener = TcpListener::bind(address).unwrap();
+    println!("Waiting for players on {}", address);
+    let record = referee::<G>(&listener, Duration::from_secs(timeout)).unwrap();
+    print_record(&record);
+    if let Some(path) = record_file {
+        record.save(&path).unwrap();
+    }
+}
+
+fn print_record(record: &GameRecord) {
+    println!(
+        "{} played {}",
+        record.players.join(" and "),
+        record.moves.join(" ")
+    );
+    if let Some(reason) = &record.forfeit {
+        println!("Forfeit: {}", reason);
+    }
+    println!("{}", record.result);
+}
+
+fn main() {
+    let args = NetArgs::parse();
+    match &args {
+        NetArgs::Serve {
+            game,
+            address,
+            record,
+            timeout,
+        } => match game {
+            GameName::TicTacToe => serve::<TicTacToe>(address, record.clone(), *timeout),
+            GameName::Connect4 => serve::<Connect4>(address, record.clone(), *timeout),
+            GameName::Othello => serve::<Othello>(address, record.clone(), *timeout),
+        },
+        NetArgs::Join {
+            game,
+            address,
+            name,
+            player,
+            depth,
+            iterations,
+            nodes,
+            data_dir,
+            model,
+            canonical,
+        } => {
+            let options = PlayerOptions {
+                depth: *depth,
+                iterations: *iterations,
+                nodes: *nodes,
+                data_dir: data_dir.clone(),
+                model: model.clone(),
+                canonical: *canonical,
+                ..Default::default()
+            };
+            match game {
+                GameName::TicTacToe => join::<TicTacToe>(address, name, player, &options),
+                GameName::Connect4 => join::<Connect4>(address, name, player, &options),
+                GameName::Othello => join::<Othello>(address, name, player, &options),
+            }
+        }
+    }
+}
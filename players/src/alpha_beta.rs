@@ -0,0 +1,273 @@
+use rust_games_shared::{Game, Player, PlayerError, PlayerId};
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crate::evaluate::{Evaluate, WIN_SCORE};
+
+// How deep a time limited search may go, and how big the transposition table may grow before it is cleared
+const MAX_DEPTH: usize = 64;
+const MAX_TABLE_ENTRIES: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy)]
+pub enum SearchBudget {
+    Depth(usize),
+    Time(Duration),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TableEntry<M> {
+    depth: usize,
+    score: f32,
+    bound: Bound,
+    best: Option<M>,
+}
+
+struct SearchState {
+    deadline: Option<Instant>,
+    nodes: usize,
+}
+
+impl SearchState {
+    fn out_of_time(&mut self) -> bool {
+        self.nodes += 1;
+        // Checking the clock on every node would slow the search down
+        self.nodes % 256 == 0 && self.deadline.map_or(false, |deadline| Instant::now() >= deadline)
+    }
+}
+
+// Entries are keyed on the position and the player to move
+type Table<G: Game> = HashMap<(G::Board, PlayerId), TableEntry<G::Move>>;
+
+/*
+Negamax search with alpha-beta pruning, iteratively deepened until the depth or time budget runs out.
+Moves are tried best first: the transposition table's move, then by the static evaluation of where they lead.
+Scores are always from the point of view of the player to move, which also copes with players moving twice
+in a row, and chance nodes are averaged over their outcomes.
+ */
+pub struct AlphaBeta<G: Game, E: Evaluate<G>> {
+    eval: E,
+    budget: SearchBudget,
+    table: RefCell<Table<G>>,
+}
+
+impl<G: Game, E: Evaluate<G>> AlphaBeta<G, E> {
+    pub fn new(eval: E, budget: SearchBudget) -> AlphaBeta<G, E> {
+        AlphaBeta {
+            eval,
+            budget,
+            table: RefCell::new(HashMap::new()),
+        }
+    }
+
+    // Returns None if the time ran out before the search finished
+    fn negamax(
+        &self,
+        game: &G,
+        depth: usize,
+        mut alpha: f32,
+        mut beta: f32,
+        search: &mut SearchState,
+    ) -> Option<f32> {
+        if search.out_of_time() {
+            return None;
+        }
+
+        let player = game.current_player();
+        if game.is_over() || depth == 0 {
+            let score = self.eval.score(game, player);
+            // Prefer quicker wins and slower losses
+            if score.abs() >= WIN_SCORE {
+                return Some(score + score.signum() * depth as f32);
+            }
+            return Some(score);
+        }
+
+        if game.is_chance_node() {
+            let mut expected = 0.0;
+            for (outcome, probability) in game.chance_outcomes() {
+                let mut next = game.clone();
+                next.make_move(outcome);
+                let value = self.negamax(&next, depth, f32::NEG_INFINITY, f32::INFINITY, search)?;
+                expected += probability * if next.current_player() == player { value } else { -value };
+            }
+            return Some(expected);
+        }
+
+        let key = (game.get_board(), player);
+        let original_alpha = alpha;
+        let table_move = match self.table.borrow().get(&key) {
+            Some(entry) => {
+                if entry.depth >= depth {
+                    match entry.bound {
+                        Bound::Exact => return Some(entry.score),
+                        Bound::Lower => alpha = alpha.max(entry.score),
+                        Bound::Upper => beta = beta.min(entry.score),
+                    }
+                    if alpha >= beta {
+                        return Some(entry.score);
+                    }
+                }
+                entry.best
+            }
+            None => None,
+        };
+
+        let mut best_score = f32::NEG_INFINITY;
+        let mut best_move = None;
+        for mv in self.ordered_moves(game, table_move, depth) {
+            let mut next = game.clone();
+            next.make_move(mv);
+
+            let score = if next.current_player() == player {
+                self.negamax(&next, depth - 1, alpha, beta, search)?
+            } else {
+                -self.negamax(&next, depth - 1, -beta, -alpha, search)?
+            };
+
+            if score > best_score {
+                best_score = score;
+                best_move = Some(mv);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        if best_move.is_none() {
+            return Some(self.eval.score(game, player));
+        }
+
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table.borrow_mut().insert(
+            key,
+            TableEntry {
+                depth,
+                score: best_score,
+                bound,
+                best: best_move,
+            },
+        );
+
+        Some(best_score)
+    }
+
+    fn ordered_moves(&self, game: &G, table_move: Option<G::Move>, depth: usize) -> Vec<G::Move> {
+        let mut moves = game.legal_moves();
+
+        // Sorting costs an evaluation per move, which only pays off with a subtree left to prune
+        if depth >= 2 {
+            let player = game.current_player();
+            let mut scored: Vec<(G::Move, f32)> = moves
+                .into_iter()
+                .map(|mv| {
+                    let mut next = game.clone();
+                    next.make_move(mv);
+                    (mv, self.eval.score(&next, player))
+                })
+                .collect();
+            scored.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+            moves = scored.into_iter().map(|(mv, _)| mv).collect();
+        }
+
+        if let Some(first) = table_move {
+            if let Some(i) = moves.iter().position(|mv| *mv == first) {
+                moves[..=i].rotate_right(1);
+            }
+        }
+        moves
+    }
+}
+
+impl<G: Game + 'static, E: Evaluate<G> + 'static> Player<G> for AlphaBeta<G, E> {
+    fn choose_move(&self, game: &G) -> Result<<G as Game>::Move, PlayerError> {
+        let moves = game.legal_moves();
+        if moves.is_empty() {
+            return Err(PlayerError::NoLegalMoves);
+        }
+        if moves.len() == 1 {
+            return Ok(moves[0]);
+        }
+
+        if self.table.borrow().len() > MAX_TABLE_ENTRIES {
+            self.table.borrow_mut().clear();
+        }
+
+        let (max_depth, deadline) = match self.budget {
+            SearchBudget::Depth(depth) => (depth, None),
+            SearchBudget::Time(time) => (MAX_DEPTH, Some(Instant::now() + time)),
+        };
+
+        let key = (game.get_board(), game.current_player());
+        let mut best = moves[0];
+        for depth in 1..=max_depth {
+            let mut search = SearchState { deadline, nodes: 0 };
+            // An unfinished iteration is thrown away in favour of the last finished one
+            let Some(score) = self.negamax(game, depth, f32::NEG_INFINITY, f32::INFINITY, &mut search) else {
+                break;
+            };
+            if let Some(mv) = self.table.borrow().get(&key).and_then(|entry| entry.best) {
+                best = mv;
+            }
+            // The result is decided, so searching deeper cannot change it
+            if score.abs() >= WIN_SCORE {
+                break;
+            }
+        }
+
+        Ok(best)
+    }
+
+    fn reset(&mut self) {
+        self.table.get_mut().clear();
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AlphaBeta, SearchBudget};
+    use crate::Heuristic;
+    use rust_games_games::Connect4;
+    use rust_games_shared::{Game, Player};
+    use std::time::Duration;
+
+    #[test]
+    fn blocks_a_vertical_threat() {
+        let mut g = Connect4::new();
+        let moves = Connect4::all_possible_moves();
+        for column in [0, 1, 0, 1, 0] {
+            g.make_move(moves[column]);
+        }
+
+        let player = AlphaBeta::new(Heuristic, SearchBudget::Depth(4));
+        assert_eq!(player.choose_move(&g).unwrap(), moves[0]);
+    }
+
+    #[test]
+    fn time_budget_returns_a_move() {
+        let g = Connect4::new();
+        let player = AlphaBeta::new(Heuristic, SearchBudget::Time(Duration::from_millis(50)));
+        assert!(g.legal_moves().contains(&player.choose_move(&g).unwrap()));
+    }
+}
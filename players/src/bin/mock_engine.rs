@@ -0,0 +1,48 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+/*
+A stand-in external engine for testing players::External. It plays Connect4, always in the leftmost open column.
+Started with "crash" it exits when asked for a move, and with "hang" it never answers.
+ */
+use rust_games_games::Connect4;
+use rust_games_players::move_notation;
+use rust_games_shared::Game;
+
+use std::io::{self, BufRead, Write};
+
+fn main() {
+    let mode = std::env::args().nth(1).unwrap_or_default();
+    let mut game = Connect4::new();
+    let mut stdout = io::stdout();
+
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("newgame") => game = Connect4::new(),
+            Some("position") => {
+                game = Connect4::new();
+                for notation in words.skip_while(|word| *word != "moves").skip(1) {
+                    let mv = game
+                        .legal_moves()
+                        .into_iter()
+                        .find(|mv| move_notation(mv) == notation)
+                        .expect("Moves should be legal");
+                    game.make_move(mv);
+                }
+            }
+            Some("go") => match mode.as_str() {
+                "crash" => std::process::exit(1),
+                "hang" => {}
+                _ => {
+                    writeln!(stdout, "info thinking").unwrap();
+                    writeln!(stdout, "bestmove {}", move_notation(&game.legal_moves()[0])).unwrap();
+                    stdout.flush().unwrap();
+                }
+            },
+            Some("quit") => break,
+            _ => {}
+        }
+    }
+}
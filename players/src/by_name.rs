@@ -0,0 +1,120 @@
+use alphazero::{AlphaZeroPlayer, BoardGameModel};
+use dfdx::prelude::*;
+use rust_games_games::{Connect4, Othello, TicTacToe};
+use rust_games_shared::{Game, Player};
+
+use crate::{
+    AlphaBeta, Connect4Solver, Connect4Threats, EvalGreedy, Heuristic, Human, OthelloEndgameSolver,
+    OthelloEval, Random, SearchBudget, TicTacToeSolver, Uct, UctBudget,
+};
+
+// What the command-line tools let users tune about the player they pick by name
+#[derive(Debug, Clone)]
+pub struct PlayerOptions {
+    // Search depth for alphabeta
+    pub depth: usize,
+    // Iterations for uct and alphazero
+    pub iterations: usize,
+    // How many positions the Connect4 solver may visit per move before falling back to a heuristic search
+    pub nodes: usize,
+    // Where to load the alphazero model from, and its name there
+    pub data_dir: Option<String>,
+    pub model: String,
+    pub temperature: f32,
+    // Whether the alphazero model was trained on positions seen from the player to move
+    pub canonical: bool,
+}
+
+impl Default for PlayerOptions {
+    fn default() -> Self {
+        PlayerOptions {
+            depth: 4,
+            iterations: 200,
+            nodes: 1_000_000,
+            data_dir: None,
+            model: "best".to_string(),
+            temperature: 0.2,
+            canonical: false,
+        }
+    }
+}
+
+/*
+Games whose players can be picked by name: human, random, greedy, alphabeta, uct or alphazero,
+along with the players written for that game alone, listed at its impl_named_players below.
+Each game has its own impl, since the alphazero player is built from the game's own model.
+ */
+pub trait NamedPlayers: Game {
+    fn named_player(name: &str, options: &PlayerOptions) -> Result<Box<dyn Player<Self>>, String>;
+
+    // The visit share of each move and the value for the first player, if the player is the alphazero one
+    fn analysis(player: &dyn Player<Self>, game: &Self) -> Option<Analysis<Self>>;
+}
+
+type Analysis<G: Game> = (Vec<(G::Move, f32)>, f32);
+type BuiltModel<G: Game> = <BoardGameModel<G> as BuildOnDevice<AutoDevice, f32>>::Built;
+
+macro_rules! impl_named_players {
+    ($game:ty, { $($name:literal => |$options:ident| $player:expr),* $(,)? }) => {
+        impl NamedPlayers for $game {
+            fn named_player(
+                name: &str,
+                options: &PlayerOptions,
+            ) -> Result<Box<dyn Player<Self>>, String> {
+                type G = $game;
+                Ok(match name {
+                    "human" => Box::new(Human::<G>::new()),
+                    "random" => Box::new(Random::<G>::new()),
+                    "greedy" => Box::new(EvalGreedy::<G, _>::new(Heuristic)),
+                    "alphabeta" => Box::new(AlphaBeta::<G, _>::new(
+                        Heuristic,
+                        SearchBudget::Depth(options.depth),
+                    )),
+                    "uct" => Box::new(Uct::<G>::new(UctBudget::Iterations(options.iterations))),
+                    "alphazero" => {
+                        let dev: AutoDevice = Default::default();
+                        let data_dir = options.data_dir.as_deref().ok_or(
+                            "The alphazero player needs a data dir to load its model from",
+                        )?;
+                        Box::new(
+                            AlphaZeroPlayer::new_from_file::<BoardGameModel<G>>(
+                                &options.model,
+                                data_dir,
+                                options.temperature,
+                                &dev,
+                                false,
+                                options.iterations,
+                            )
+                            .with_canonical(options.canonical),
+                        )
+                    }
+                    $($name => {
+                        let $options = options;
+                        Box::new($player)
+                    })*
+                    other => return Err(format!("Unknown player {}", other)),
+                })
+            }
+
+            fn analysis(player: &dyn Player<Self>, game: &Self) -> Option<Analysis<Self>> {
+                let az = player
+                    .as_any()
+                    .downcast_ref::<AlphaZeroPlayer<$game, BuiltModel<$game>>>()?;
+                Some(az.mcts.borrow_mut().analyze(game))
+            }
+        }
+    };
+}
+
+impl_named_players!(TicTacToe, {
+    "solver" => |_options| TicTacToeSolver::new(),
+});
+impl_named_players!(Connect4, {
+    "solver" => |options| Connect4Solver::new(options.nodes),
+    "threats" => |_options| Connect4Threats::new(),
+});
+impl_named_players!(Othello, {
+    "eval" => |options| AlphaBeta::new(OthelloEval::default(), SearchBudget::Depth(options.depth)),
+    // Searches to the end once 12 squares are left
+    "solver" => |_options| OthelloEndgameSolver::new(12),
+});
@@ -0,0 +1,332 @@
+use rust_games_games::{Connect4, Connect4Move, Connect4State};
+use rust_games_shared::{Game, Player, PlayerError, PlayerId};
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+
+use crate::{AlphaBeta, Heuristic, SearchBudget};
+
+pub(crate) const WIDTH: usize = 7;
+pub(crate) const HEIGHT: usize = 8;
+const CELLS: i32 = (WIDTH * HEIGHT) as i32;
+
+// Columns near the middle take part in more lines, so they are tried first
+const COLUMN_ORDER: [usize; WIDTH] = [3, 2, 4, 1, 5, 0, 6];
+
+const fn bottom_row() -> u64 {
+    let mut mask = 0;
+    let mut col = 0;
+    while col < WIDTH {
+        mask |= 1 << (col * (HEIGHT + 1));
+        col += 1;
+    }
+    mask
+}
+
+pub(crate) const BOTTOM: u64 = bottom_row();
+const BOARD: u64 = BOTTOM * ((1 << HEIGHT) - 1);
+
+pub(crate) fn column_mask(col: usize) -> u64 {
+    ((1 << HEIGHT) - 1) << (col * (HEIGHT + 1))
+}
+
+/*
+A Connect4 position as two bitboards, one column after another with an always empty bit on top of each.
+position holds the stones of the player to move and mask all stones, so playing a move is two operations.
+ */
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Bitboard {
+    pub(crate) position: u64,
+    pub(crate) mask: u64,
+    pub(crate) moves: i32,
+}
+
+impl Bitboard {
+    pub(crate) fn from_game(game: &Connect4) -> Bitboard {
+        let mine = match game.current_player() {
+            PlayerId::First => Connect4State::First,
+            PlayerId::Second => Connect4State::Second,
+        };
+
+        let mut board = Bitboard {
+            position: 0,
+            mask: 0,
+            moves: 0,
+        };
+        for (row, tiles) in game.get_board().iter().enumerate() {
+            for (col, tile) in tiles.iter().enumerate() {
+                if *tile == Connect4State::Empty {
+                    continue;
+                }
+                let bit = 1 << (col * (HEIGHT + 1) + row);
+                board.mask |= bit;
+                board.moves += 1;
+                if *tile == mine {
+                    board.position |= bit;
+                }
+            }
+        }
+        board
+    }
+
+    pub(crate) fn key(&self) -> u64 {
+        self.position + self.mask
+    }
+
+    // The bit each column's next stone would land on
+    pub(crate) fn possible(&self) -> u64 {
+        (self.mask + BOTTOM) & BOARD
+    }
+
+    pub(crate) fn play(&mut self, mv: u64) {
+        self.position ^= self.mask;
+        self.mask |= mv;
+        self.moves += 1;
+    }
+
+    pub(crate) fn opponent_position(&self) -> u64 {
+        self.position ^ self.mask
+    }
+
+    pub(crate) fn can_win_next(&self) -> bool {
+        winning_squares(self.position, self.mask) & self.possible() != 0
+    }
+
+    // Moves that don't hand the opponent an immediate win, assuming the player to move can't win at once
+    pub(crate) fn non_losing_moves(&self) -> u64 {
+        let mut possible = self.possible();
+        let threats = winning_squares(self.opponent_position(), self.mask);
+        let forced = possible & threats;
+        if forced != 0 {
+            // Two threats to block can't both be stopped
+            if forced & (forced - 1) != 0 {
+                return 0;
+            }
+            possible = forced;
+        }
+        // Never play just below a square the opponent wins on
+        possible & !(threats >> 1)
+    }
+}
+
+// Empty squares that would complete four in a row for the stones in position
+pub(crate) fn winning_squares(position: u64, mask: u64) -> u64 {
+    // Vertical
+    let mut r = (position << 1) & (position << 2) & (position << 3);
+
+    // Horizontal, then both diagonals
+    for shift in [HEIGHT + 1, HEIGHT, HEIGHT + 2] {
+        let mut p = (position << shift) & (position << (2 * shift));
+        r |= p & (position << (3 * shift));
+        r |= p & (position >> shift);
+        p = (position >> shift) & (position >> (2 * shift));
+        r |= p & (position << shift);
+        r |= p & (position >> (3 * shift));
+    }
+
+    r & (BOARD ^ mask)
+}
+
+// The game-theoretic value of a position and a move that achieves it
+#[derive(Debug, Clone, Copy)]
+pub struct Connect4Solution {
+    /*
+    Positive if the player to move wins, negative if they lose and zero for a draw.
+    The size is one more than the number of stones the winner has left once they win, so sooner wins score higher.
+     */
+    pub score: i32,
+    pub best_move: Connect4Move,
+}
+
+/*
+Solves Connect4 exactly with alpha-beta search over bitboards, narrowing in on the score with null-window searches.
+A transposition table keeps upper bounds between searches. Each solve may only visit so many nodes,
+and when it runs out the player falls back to a shallow heuristic search instead.
+ */
+pub struct Connect4Solver {
+    node_budget: usize,
+    nodes: Cell<usize>,
+    table: RefCell<HashMap<u64, i32>>,
+    fallback: AlphaBeta<Connect4, Heuristic>,
+}
+
+impl Connect4Solver {
+    pub fn new(node_budget: usize) -> Connect4Solver {
+        Connect4Solver {
+            node_budget,
+            nodes: Cell::new(0),
+            table: RefCell::new(HashMap::new()),
+            fallback: AlphaBeta::new(Heuristic, SearchBudget::Depth(6)),
+        }
+    }
+
+    // None if the game is over or the node budget ran out
+    pub fn solve(&self, game: &Connect4) -> Option<Connect4Solution> {
+        if game.is_over() {
+            return None;
+        }
+        self.nodes.set(0);
+
+        let board = Bitboard::from_game(game);
+        let moves = Connect4::all_possible_moves();
+
+        // Nothing beats winning straight away, so there's no need to search the other columns
+        let wins = winning_squares(board.position, board.mask) & board.possible();
+        if let Some(col) = (0..WIDTH).find(|col| wins & column_mask(*col) != 0) {
+            return Some(Connect4Solution {
+                score: (CELLS + 1 - board.moves) / 2,
+                best_move: moves[col],
+            });
+        }
+
+        let mut best: Option<Connect4Solution> = None;
+        for col in COLUMN_ORDER {
+            let mv = board.possible() & column_mask(col);
+            if mv == 0 {
+                continue;
+            }
+
+            let mut next = board;
+            next.play(mv);
+            let score = -self.solve_bitboard(&next)?;
+            if best.map_or(true, |best| score > best.score) {
+                best = Some(Connect4Solution {
+                    score,
+                    best_move: moves[col],
+                });
+            }
+        }
+        best
+    }
+
+    fn solve_bitboard(&self, board: &Bitboard) -> Option<i32> {
+        if board.can_win_next() {
+            return Some((CELLS + 1 - board.moves) / 2);
+        }
+
+        // Each search only asks whether the score is above a guess, halving the range it could be in
+        let mut min = -(CELLS - board.moves) / 2;
+        let mut max = (CELLS + 1 - board.moves) / 2;
+        while min < max {
+            let mut guess = min + (max - min) / 2;
+            if guess <= 0 && min / 2 < guess {
+                guess = min / 2;
+            } else if guess >= 0 && max / 2 > guess {
+                guess = max / 2;
+            }
+
+            let score = self.negamax(board, guess, guess + 1)?;
+            if score <= guess {
+                max = score;
+            } else {
+                min = score;
+            }
+        }
+        Some(min)
+    }
+
+    // Assumes the player to move can't win at once
+    fn negamax(&self, board: &Bitboard, mut alpha: i32, mut beta: i32) -> Option<i32> {
+        self.nodes.set(self.nodes.get() + 1);
+        if self.nodes.get() > self.node_budget {
+            return None;
+        }
+
+        let next = board.non_losing_moves();
+        if next == 0 {
+            return Some(-(CELLS - board.moves) / 2);
+        }
+        if board.moves >= CELLS - 2 {
+            return Some(0);
+        }
+
+        let min = -(CELLS - 2 - board.moves) / 2;
+        if alpha < min {
+            alpha = min;
+            if alpha >= beta {
+                return Some(alpha);
+            }
+        }
+        let max = match self.table.borrow().get(&board.key()) {
+            Some(upper) => *upper,
+            None => (CELLS - 1 - board.moves) / 2,
+        };
+        if beta > max {
+            beta = max;
+            if alpha >= beta {
+                return Some(beta);
+            }
+        }
+
+        // Moves that make the most new threats go first
+        let mut ordered: Vec<(u64, u32)> = COLUMN_ORDER
+            .iter()
+            .map(|col| next & column_mask(*col))
+            .filter(|mv| *mv != 0)
+            .map(|mv| (mv, winning_squares(board.position | mv, board.mask).count_ones()))
+            .collect();
+        ordered.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        for (mv, _) in ordered {
+            let mut child = *board;
+            child.play(mv);
+            let score = -self.negamax(&child, -beta, -alpha)?;
+            if score >= beta {
+                return Some(score);
+            }
+            alpha = alpha.max(score);
+        }
+
+        self.table.borrow_mut().insert(board.key(), alpha);
+        Some(alpha)
+    }
+}
+
+impl Player<Connect4> for Connect4Solver {
+    fn choose_move(&self, game: &Connect4) -> Result<Connect4Move, PlayerError> {
+        match self.solve(game) {
+            Some(solution) => Ok(solution.best_move),
+            None => self.fallback.choose_move(game),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.table.get_mut().clear();
+        self.fallback.reset();
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Connect4Solver;
+    use rust_games_games::Connect4;
+    use rust_games_shared::{Game, Player};
+
+    #[test]
+    fn finds_the_quickest_win() {
+        let mut g = Connect4::new();
+        let moves = Connect4::all_possible_moves();
+        for column in [0, 1, 0, 1, 0, 1] {
+            g.make_move(moves[column]);
+        }
+
+        let solution = Connect4Solver::new(1_000_000).solve(&g).unwrap();
+        assert_eq!(solution.best_move, moves[0]);
+        // First wins with their fourth stone, leaving 28 - 4 unplayed
+        assert_eq!(solution.score, 25);
+    }
+
+    #[test]
+    fn falls_back_when_out_of_nodes() {
+        let g = Connect4::new();
+        let solver = Connect4Solver::new(10);
+        assert!(solver.solve(&g).is_none());
+        assert!(g.legal_moves().contains(&solver.choose_move(&g).unwrap()));
+    }
+}
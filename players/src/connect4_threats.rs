@@ -0,0 +1,128 @@
+use rand::seq::SliceRandom;
+use rust_games_games::{Connect4, Connect4Move};
+use rust_games_shared::{Game, Player, PlayerError, PlayerId};
+
+use crate::connect4_solver::{column_mask, winning_squares, Bitboard, BOTTOM, WIDTH};
+
+// Rows 1, 3, 5 and 7 counting from the bottom, where the first player's threats tend to come true
+const ODD_ROWS: u64 = BOTTOM * 0b0101_0101;
+
+// Central columns are part of more lines
+const COLUMN_VALUES: [f32; WIDTH] = [0.0, 1.0, 2.0, 3.0, 2.0, 1.0, 0.0];
+
+// A threat is an empty square that would complete four, and it's worth more on the rows its owner can force
+fn threat_value(threats: u64, player: PlayerId) -> f32 {
+    let good_rows = match player {
+        PlayerId::First => ODD_ROWS,
+        PlayerId::Second => !ODD_ROWS,
+    };
+    (threats & good_rows).count_ones() as f32 * 3.0 + (threats & !good_rows).count_ones() as f32
+}
+
+/*
+A cheap Connect4 bot: it wins when it can, blocks when it must and never plays right under an opponent's threat.
+Otherwise it plays the move leaving it the best open threes compared to the opponent's, counting threats the
+player can force by the odd/even row rule for more, with the centre breaking ties.
+ */
+pub struct Connect4Threats;
+
+impl Connect4Threats {
+    pub fn new() -> Connect4Threats {
+        Connect4Threats
+    }
+}
+
+impl Default for Connect4Threats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player<Connect4> for Connect4Threats {
+    fn choose_move(&self, game: &Connect4) -> Result<Connect4Move, PlayerError> {
+        let moves = Connect4::all_possible_moves();
+        let board = Bitboard::from_game(game);
+        let possible = board.possible();
+        let columns = |squares: u64| -> Vec<usize> {
+            (0..WIDTH)
+                .filter(|col| squares & column_mask(*col) != 0)
+                .collect()
+        };
+
+        if possible == 0 {
+            return Err(PlayerError::NoLegalMoves);
+        }
+
+        // Win, or failing that block the opponent's win
+        let wins = winning_squares(board.position, board.mask) & possible;
+        if let Some(col) = columns(wins).first() {
+            return Ok(moves[*col]);
+        }
+        let blocks = winning_squares(board.opponent_position(), board.mask) & possible;
+        if let Some(col) = columns(blocks).first() {
+            return Ok(moves[*col]);
+        }
+
+        // If every move loses anyway, still play one
+        let safe = match board.non_losing_moves() {
+            0 => possible,
+            safe => safe,
+        };
+
+        let player = game.current_player();
+        let scored: Vec<(usize, f32)> = columns(safe)
+            .into_iter()
+            .map(|col| {
+                let mut next = board;
+                next.play(possible & column_mask(col));
+                let mine = winning_squares(next.opponent_position(), next.mask);
+                let theirs = winning_squares(next.position, next.mask);
+                let score = threat_value(mine, player) - threat_value(theirs, player.opponent())
+                    + COLUMN_VALUES[col] * 0.1;
+                (col, score)
+            })
+            .collect();
+
+        let best = scored
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let best_columns: Vec<usize> = scored
+            .iter()
+            .filter(|(_, score)| *score == best)
+            .map(|(col, _)| *col)
+            .collect();
+        Ok(moves[*best_columns.choose(&mut rand::thread_rng()).unwrap()])
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Connect4Threats;
+    use rust_games_games::Connect4;
+    use rust_games_shared::{Game, Player};
+
+    #[test]
+    fn wins_before_blocking() {
+        let mut g = Connect4::new();
+        let moves = Connect4::all_possible_moves();
+        for column in [0, 1, 0, 1, 0, 1] {
+            g.make_move(moves[column]);
+        }
+        assert_eq!(Connect4Threats::new().choose_move(&g).unwrap(), moves[0]);
+    }
+
+    #[test]
+    fn blocks_a_threat() {
+        let mut g = Connect4::new();
+        let moves = Connect4::all_possible_moves();
+        for column in [0, 1, 0, 1, 0] {
+            g.make_move(moves[column]);
+        }
+        assert_eq!(Connect4Threats::new().choose_move(&g).unwrap(), moves[0]);
+    }
+}
@@ -0,0 +1,158 @@
+use rust_games_shared::{Game, Player, PlayerError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vote {
+    // Every member's move counts once
+    Majority,
+    // Every member's move counts its weight
+    Weighted,
+    // The weighted mean of the members' policies. Members without one stand behind their chosen move alone
+    PolicyAverage,
+}
+
+/*
+A committee of players that each look at the position and vote on the move to play.
+Ties go to the move the earliest member voted for, so a committee of deterministic players is deterministic.
+ */
+pub struct Ensemble<G: Game> {
+    members: Vec<(Box<dyn Player<G>>, f32)>,
+    vote: Vote,
+}
+
+impl<G: Game> Ensemble<G> {
+    pub fn new(vote: Vote) -> Ensemble<G> {
+        Ensemble {
+            members: vec![],
+            vote,
+        }
+    }
+
+    pub fn with_member(mut self, player: impl Player<G> + 'static, weight: f32) -> Ensemble<G> {
+        assert!(weight >= 0.0, "Weights can't be negative");
+        self.members.push((Box::new(player), weight));
+        self
+    }
+
+    // Without a positive weight there is nothing to weigh the votes by, and the averaged policy would divide by zero
+    fn check_weights(&self) {
+        assert!(
+            self.vote == Vote::Majority || self.members.iter().any(|(_, weight)| *weight > 0.0),
+            "An ensemble needs a member with a positive weight"
+        );
+    }
+
+    fn ballot(&self, player: &dyn Player<G>, game: &G) -> Result<Vec<(G::Move, f32)>, PlayerError> {
+        if self.vote == Vote::PolicyAverage {
+            if let Some(policy) = player.policy(game) {
+                return Ok(policy);
+            }
+        }
+        Ok(vec![(player.choose_move(game)?, 1.0)])
+    }
+}
+
+impl<G: Game + 'static> Player<G> for Ensemble<G> {
+    fn choose_move(&self, game: &G) -> Result<G::Move, PlayerError> {
+        self.check_weights();
+        let mut tally: Vec<(G::Move, f32)> = vec![];
+        for (player, weight) in &self.members {
+            let weight = match self.vote {
+                Vote::Majority => 1.0,
+                Vote::Weighted | Vote::PolicyAverage => *weight,
+            };
+            for (mv, share) in self.ballot(player.as_ref(), game)? {
+                match tally.iter_mut().find(|(voted, _)| *voted == mv) {
+                    Some((_, votes)) => *votes += weight * share,
+                    None => tally.push((mv, weight * share)),
+                }
+            }
+        }
+
+        // max_by would keep the last of equal votes
+        tally
+            .into_iter()
+            .rev()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(mv, _)| mv)
+            .ok_or(PlayerError::NoLegalMoves)
+    }
+
+    fn reset(&mut self) {
+        for (player, _) in self.members.iter_mut() {
+            player.reset();
+        }
+    }
+
+    fn policy(&self, game: &G) -> Option<Vec<(G::Move, f32)>> {
+        if self.vote != Vote::PolicyAverage {
+            return None;
+        }
+        self.check_weights();
+        let total: f32 = self.members.iter().map(|(_, weight)| weight).sum();
+        let mut average: Vec<(G::Move, f32)> = vec![];
+        for (player, weight) in &self.members {
+            for (mv, share) in self.ballot(player.as_ref(), game).ok()? {
+                match average.iter_mut().find(|(voted, _)| *voted == mv) {
+                    Some((_, p)) => *p += weight * share / total,
+                    None => average.push((mv, weight * share / total)),
+                }
+            }
+        }
+        Some(average)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Ensemble, Vote};
+    use crate::{Connect4Threats, EvalSoftmax, Heuristic, Random};
+    use rust_games_games::Connect4;
+    use rust_games_shared::{Game, Player};
+
+    #[test]
+    fn majority_outvotes_a_random_member() {
+        let mut g = Connect4::new();
+        let moves = Connect4::all_possible_moves();
+        for column in [0, 1, 0, 1, 0, 1] {
+            g.make_move(moves[column]);
+        }
+
+        let committee = Ensemble::new(Vote::Majority)
+            .with_member(Random::new(), 1.0)
+            .with_member(Connect4Threats::new(), 1.0)
+            .with_member(Connect4Threats::new(), 1.0);
+        assert_eq!(committee.choose_move(&g).unwrap(), moves[0]);
+    }
+
+    #[test]
+    fn weights_and_policies_decide() {
+        let g = Connect4::new();
+        let moves = Connect4::all_possible_moves();
+
+        let weighted = Ensemble::new(Vote::Weighted)
+            .with_member(Connect4Threats::new(), 3.0)
+            .with_member(Random::new(), 1.0)
+            .with_member(Random::new(), 1.0);
+        assert_eq!(weighted.choose_move(&g).unwrap(), moves[3]);
+
+        let averaged = Ensemble::new(Vote::PolicyAverage)
+            .with_member(EvalSoftmax::new(Heuristic, 1.0), 1.0)
+            .with_member(Connect4Threats::new(), 1.0);
+        let policy = averaged.policy(&g).unwrap();
+        let total: f32 = policy.iter().map(|(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    #[should_panic(expected = "positive weight")]
+    fn zero_weights_are_rejected() {
+        let committee = Ensemble::new(Vote::PolicyAverage)
+            .with_member(EvalSoftmax::new(Heuristic, 1.0), 0.0)
+            .with_member(Connect4Threats::new(), 0.0);
+        committee.policy(&Connect4::new());
+    }
+}
@@ -0,0 +1,144 @@
+use rand::{distributions::WeightedIndex, prelude::Distribution, seq::SliceRandom};
+use rust_games_shared::{Game, Player, PlayerError};
+
+use std::marker::PhantomData;
+
+use crate::evaluate::{successor_scores, Evaluate};
+
+/*
+Plays the move leading to the best scoring position, breaking ties at random.
+ */
+pub struct EvalGreedy<G: Game, E: Evaluate<G>> {
+    eval: E,
+    game: PhantomData<G>,
+}
+
+impl<G: Game, E: Evaluate<G>> EvalGreedy<G, E> {
+    pub fn new(eval: E) -> EvalGreedy<G, E> {
+        EvalGreedy {
+            eval,
+            game: PhantomData,
+        }
+    }
+}
+
+impl<G: Game + 'static, E: Evaluate<G> + 'static> Player<G> for EvalGreedy<G, E> {
+    fn choose_move(&self, game: &G) -> Result<<G as Game>::Move, PlayerError> {
+        let scores = successor_scores(&self.eval, game);
+
+        let best = scores
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let best_moves: Vec<G::Move> = scores
+            .iter()
+            .filter(|(_, score)| *score == best)
+            .map(|(mv, _)| *mv)
+            .collect();
+
+        match best_moves.choose(&mut rand::thread_rng()) {
+            Some(mv) => Ok(*mv),
+            None => Err(PlayerError::NoLegalMoves),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/*
+Samples a move with probability proportional to exp(score / temperature) of the position it leads to.
+Low temperatures play like EvalGreedy, high ones like Random.
+ */
+pub struct EvalSoftmax<G: Game, E: Evaluate<G>> {
+    eval: E,
+    temperature: f32,
+    game: PhantomData<G>,
+}
+
+impl<G: Game, E: Evaluate<G>> EvalSoftmax<G, E> {
+    pub fn new(eval: E, temperature: f32) -> EvalSoftmax<G, E> {
+        assert!(temperature > 0.0, "The temperature must be positive");
+        EvalSoftmax {
+            eval,
+            temperature,
+            game: PhantomData,
+        }
+    }
+
+    fn distribution(&self, game: &G) -> Vec<(G::Move, f32)> {
+        let scores = successor_scores(&self.eval, game);
+
+        // Shifting by the best score keeps the exponentials in range
+        let best = scores
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let weights: Vec<(G::Move, f32)> = scores
+            .iter()
+            .map(|(mv, score)| (*mv, ((score - best) / self.temperature).exp()))
+            .collect();
+        let total: f32 = weights.iter().map(|(_, weight)| weight).sum();
+        weights
+            .into_iter()
+            .map(|(mv, weight)| (mv, weight / total))
+            .collect()
+    }
+}
+
+impl<G: Game + 'static, E: Evaluate<G> + 'static> Player<G> for EvalSoftmax<G, E> {
+    fn choose_move(&self, game: &G) -> Result<<G as Game>::Move, PlayerError> {
+        let distribution = self.distribution(game);
+        if distribution.is_empty() {
+            return Err(PlayerError::NoLegalMoves);
+        }
+
+        let dist = WeightedIndex::new(distribution.iter().map(|(_, p)| *p)).unwrap();
+        Ok(distribution[dist.sample(&mut rand::thread_rng())].0)
+    }
+
+    fn policy(&self, game: &G) -> Option<Vec<(G::Move, f32)>> {
+        Some(self.distribution(game)).filter(|distribution| !distribution.is_empty())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EvalGreedy, EvalSoftmax};
+    use crate::Heuristic;
+    use rust_games_games::Connect4;
+    use rust_games_shared::{Game, GameResult, Player, PlayerId};
+
+    fn three_in_first_column() -> Connect4 {
+        let mut g = Connect4::new();
+        let moves = Connect4::all_possible_moves();
+        for column in [0, 1, 0, 1, 0, 1] {
+            g.make_move(moves[column]);
+        }
+        g
+    }
+
+    #[test]
+    fn greedy_takes_the_win() {
+        let mut g = three_in_first_column();
+        let player = EvalGreedy::new(Heuristic);
+        g.make_move(player.choose_move(&g).unwrap());
+        assert!(matches!(
+            g.get_result(),
+            Some(GameResult::Winner(PlayerId::First))
+        ));
+    }
+
+    #[test]
+    fn cold_softmax_takes_the_win() {
+        let mut g = three_in_first_column();
+        let player = EvalSoftmax::new(Heuristic, 0.01);
+        g.make_move(player.choose_move(&g).unwrap());
+        assert!(g.is_over());
+    }
+}
@@ -0,0 +1,328 @@
+use rust_games_games::{
+    Adjudicator, Amazons, AmazonsBoard, AmazonsTile, Backgammon, BlokusDuo, Breakthrough, Connect4,
+    Connect4State, DotsAndBoxes, Misere, MoveLimit, Othello, OthelloState, Quoridor, TTTState,
+    TicTacToe, WithHistory,
+};
+use rust_games_shared::{Game, GameResult, PlayerId};
+
+use std::fmt::Debug;
+
+// What a won game is worth, far beyond any heuristic score
+pub const WIN_SCORE: f32 = 1.0e6;
+
+/*
+A static score for a position from one player's point of view, where higher is better for them.
+Scores should be zero-sum, so the opponent's view of a position is its negation.
+ */
+pub trait Evaluate<G: Game> {
+    fn evaluate(&self, game: &G, player: PlayerId) -> f32;
+
+    // Like evaluate, but finished games are scored by their result
+    fn score(&self, game: &G, player: PlayerId) -> f32 {
+        if !game.is_over() {
+            return self.evaluate(game, player);
+        }
+        match game.get_result() {
+            Some(GameResult::Winner(winner)) if winner == player => WIN_SCORE,
+            Some(GameResult::Winner(_)) => -WIN_SCORE,
+            Some(GameResult::Ranking(ranking)) if ranking.first() == Some(&player) => WIN_SCORE,
+            Some(GameResult::Ranking(_)) => -WIN_SCORE,
+            Some(GameResult::Tie) | None => 0.0,
+        }
+    }
+}
+
+// Scores every legal move by the position it leads to, from the mover's point of view
+pub(crate) fn successor_scores<G: Game, E: Evaluate<G>>(eval: &E, game: &G) -> Vec<(G::Move, f32)> {
+    let player = game.current_player();
+    game.legal_moves()
+        .into_iter()
+        .map(|mv| {
+            let mut next = game.clone();
+            next.make_move(mv);
+            (mv, eval.score(&next, player))
+        })
+        .collect()
+}
+
+/*
+Decides a game cut short by a MoveLimit with a static evaluation from the first player's side.
+Scores within margin of zero are draws, so a small edge doesn't count as a win.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct AdjudicateByEval<E> {
+    eval: E,
+    margin: f32,
+}
+
+impl<E> AdjudicateByEval<E> {
+    pub fn new(eval: E, margin: f32) -> AdjudicateByEval<E> {
+        assert!(margin >= 0.0, "The margin can't be negative");
+        AdjudicateByEval { eval, margin }
+    }
+}
+
+impl<G: Game, E: Evaluate<G> + Clone + Debug + Default> Adjudicator<G> for AdjudicateByEval<E> {
+    fn adjudicate(&self, game: &G) -> GameResult {
+        let score = self.eval.evaluate(game, PlayerId::First);
+        if score > self.margin {
+            GameResult::Winner(PlayerId::First)
+        } else if score < -self.margin {
+            GameResult::Winner(PlayerId::Second)
+        } else {
+            GameResult::Tie
+        }
+    }
+}
+
+// A hand-written evaluation for each perfect information game
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Heuristic;
+
+fn sign(player: PlayerId) -> f32 {
+    match player {
+        PlayerId::First => 1.0,
+        PlayerId::Second => -1.0,
+    }
+}
+
+// A line that only one side has pieces in is worth more the fuller it is
+fn line_score(mine: usize, theirs: usize) -> f32 {
+    match (mine, theirs) {
+        (m, 0) => (m * m) as f32,
+        (0, t) => -((t * t) as f32),
+        _ => 0.0,
+    }
+}
+
+impl Evaluate<TicTacToe> for Heuristic {
+    fn evaluate(&self, game: &TicTacToe, player: PlayerId) -> f32 {
+        let board = game.get_board();
+        let (mine, theirs) = match player {
+            PlayerId::First => (TTTState::X, TTTState::O),
+            PlayerId::Second => (TTTState::O, TTTState::X),
+        };
+
+        let mut lines: Vec<[(usize, usize); 3]> = vec![];
+        for i in 0..3 {
+            lines.push([(i, 0), (i, 1), (i, 2)]);
+            lines.push([(0, i), (1, i), (2, i)]);
+        }
+        lines.push([(0, 0), (1, 1), (2, 2)]);
+        lines.push([(0, 2), (1, 1), (2, 0)]);
+
+        lines
+            .iter()
+            .map(|line| {
+                let count = |state| line.iter().filter(|(x, y)| board[*x][*y] == state).count();
+                line_score(count(mine), count(theirs))
+            })
+            .sum()
+    }
+}
+
+impl Evaluate<Connect4> for Heuristic {
+    fn evaluate(&self, game: &Connect4, player: PlayerId) -> f32 {
+        let board = game.get_board();
+        let (mine, theirs) = match player {
+            PlayerId::First => (Connect4State::First, Connect4State::Second),
+            PlayerId::Second => (Connect4State::Second, Connect4State::First),
+        };
+
+        // Every window of four in a row, column or diagonal
+        let mut total = 0.0;
+        for (dr, dc) in [(0_isize, 1_isize), (1, 0), (1, 1), (1, -1)] {
+            for row in 0..8_isize {
+                for col in 0..7_isize {
+                    let (end_row, end_col) = (row + 3 * dr, col + 3 * dc);
+                    if !(0..8).contains(&end_row) || !(0..7).contains(&end_col) {
+                        continue;
+                    }
+                    let window = (0..4).map(|i| board[(row + i * dr) as usize][(col + i * dc) as usize]);
+                    let count_mine = window.clone().filter(|tile| *tile == mine).count();
+                    let count_theirs = window.filter(|tile| *tile == theirs).count();
+                    total += line_score(count_mine, count_theirs);
+                }
+            }
+        }
+        total
+    }
+}
+
+impl Evaluate<Othello> for Heuristic {
+    // Disc count, with corners worth much more since they can never be flipped
+    fn evaluate(&self, game: &Othello, player: PlayerId) -> f32 {
+        let board = game.get_board();
+        let mine = match player {
+            PlayerId::First => OthelloState::Black,
+            PlayerId::Second => OthelloState::White,
+        };
+
+        let mut total = 0.0;
+        for (x, row) in board.iter().enumerate() {
+            for (y, tile) in row.iter().enumerate() {
+                let weight = if (x == 0 || x == 7) && (y == 0 || y == 7) { 10.0 } else { 1.0 };
+                if *tile == mine {
+                    total += weight;
+                } else if *tile != OthelloState::Empty {
+                    total -= weight;
+                }
+            }
+        }
+        total
+    }
+}
+
+impl<const N: usize> Evaluate<Breakthrough<N>> for Heuristic
+where
+    Breakthrough<N>: Game<Board = [[Option<PlayerId>; N]; N]>,
+{
+    // Material, plus how far each piece has advanced
+    fn evaluate(&self, game: &Breakthrough<N>, player: PlayerId) -> f32 {
+        let mut total = 0.0;
+        for (y, row) in game.get_board().iter().enumerate() {
+            for owner in row.iter().flatten() {
+                let advanced = match owner {
+                    PlayerId::First => y,
+                    PlayerId::Second => N - 1 - y,
+                };
+                let value = 10.0 + advanced as f32;
+                total += if *owner == player { value } else { -value };
+            }
+        }
+        total
+    }
+}
+
+impl<const W: usize, const H: usize> Evaluate<DotsAndBoxes<W, H>> for Heuristic
+where
+    DotsAndBoxes<W, H>: Game,
+{
+    fn evaluate(&self, game: &DotsAndBoxes<W, H>, player: PlayerId) -> f32 {
+        let (first, second) = game.scores();
+        sign(player) * (first as f32 - second as f32)
+    }
+}
+
+impl<const N: usize> Evaluate<Amazons<N>> for Heuristic
+where
+    Amazons<N>: Game<Board = AmazonsBoard<N>>,
+{
+    // Mobility: empty squares next to each side's amazons
+    fn evaluate(&self, game: &Amazons<N>, player: PlayerId) -> f32 {
+        let board = game.get_board().tiles;
+        let mut total = 0.0;
+        for y in 0..N {
+            for x in 0..N {
+                let AmazonsTile::Amazon(owner) = board[y][x] else {
+                    continue;
+                };
+                let mut free = 0;
+                for dy in -1..=1_isize {
+                    for dx in -1..=1_isize {
+                        let (nx, ny) = (x as isize + dx, y as isize + dy);
+                        if (dx, dy) != (0, 0)
+                            && (0..N as isize).contains(&nx)
+                            && (0..N as isize).contains(&ny)
+                            && board[ny as usize][nx as usize] == AmazonsTile::Empty
+                        {
+                            free += 1;
+                        }
+                    }
+                }
+                total += if owner == player { free as f32 } else { -(free as f32) };
+            }
+        }
+        total
+    }
+}
+
+impl Evaluate<Quoridor> for Heuristic {
+    // The race: how many steps the opponent needs beyond what the player needs
+    fn evaluate(&self, game: &Quoridor, player: PlayerId) -> f32 {
+        let distance = |p| game.distance_to_goal(p).unwrap_or(usize::MAX / 2) as f32;
+        distance(player.opponent()) - distance(player)
+    }
+}
+
+impl Evaluate<BlokusDuo> for Heuristic {
+    fn evaluate(&self, game: &BlokusDuo, player: PlayerId) -> f32 {
+        (game.score(player) - game.score(player.opponent())) as f32
+    }
+}
+
+impl Evaluate<Backgammon> for Heuristic {
+    // The race: pips the opponent still has to travel beyond the player's
+    fn evaluate(&self, game: &Backgammon, player: PlayerId) -> f32 {
+        game.pip_count(player.opponent()) as f32 - game.pip_count(player) as f32
+    }
+}
+
+impl<G: Game> Evaluate<Misere<G>> for Heuristic
+where
+    Misere<G>: Game,
+    Heuristic: Evaluate<G>,
+{
+    // Whatever is good in the original game is bad here
+    fn evaluate(&self, game: &Misere<G>, player: PlayerId) -> f32 {
+        -self.evaluate(game.inner(), player)
+    }
+}
+
+impl<G: Game, A: Adjudicator<G>, const MAX_PLIES: usize> Evaluate<MoveLimit<G, MAX_PLIES, A>>
+    for Heuristic
+where
+    MoveLimit<G, MAX_PLIES, A>: Game,
+    Heuristic: Evaluate<G>,
+{
+    fn evaluate(&self, game: &MoveLimit<G, MAX_PLIES, A>, player: PlayerId) -> f32 {
+        self.evaluate(game.inner(), player)
+    }
+}
+
+impl<G: Game, const K: usize> Evaluate<WithHistory<G, K>> for Heuristic
+where
+    WithHistory<G, K>: Game,
+    Heuristic: Evaluate<G>,
+{
+    fn evaluate(&self, game: &WithHistory<G, K>, player: PlayerId) -> f32 {
+        self.evaluate(game.inner(), player)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AdjudicateByEval, Heuristic};
+    use rust_games_games::{DotsAndBoxes, DotsAndBoxesMove, MoveLimit};
+    use rust_games_shared::{Game, GameResult, PlayerId};
+
+    #[test]
+    fn adjudicates_by_evaluation() {
+        // The second player closes the first box on the fourth move, when the limit is reached
+        let moves = [
+            DotsAndBoxesMove::Horizontal(0, 0),
+            DotsAndBoxesMove::Horizontal(0, 1),
+            DotsAndBoxesMove::Vertical(0, 0),
+            DotsAndBoxesMove::Vertical(1, 0),
+        ];
+        let play = |adjudicator| {
+            let mut g: MoveLimit<DotsAndBoxes<2, 2>, 4, AdjudicateByEval<Heuristic>> =
+                MoveLimit::with_adjudicator(DotsAndBoxes::new(), adjudicator);
+            for m in moves {
+                g.make_move(m);
+            }
+            assert!(g.is_over());
+            g.get_result()
+        };
+
+        assert!(matches!(
+            play(AdjudicateByEval::default()),
+            Some(GameResult::Winner(PlayerId::Second))
+        ));
+        // One box isn't enough of a lead with a wider margin
+        assert!(matches!(
+            play(AdjudicateByEval::new(Heuristic, 1.5)),
+            Some(GameResult::Tie)
+        ));
+    }
+}
@@ -0,0 +1,301 @@
+use rust_games_shared::{Game, Player, PlayerError};
+
+use std::{
+    cell::RefCell,
+    fmt::Display,
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+// How long past its time budget an engine may take to answer before it counts as hung
+const GRACE: Duration = Duration::from_secs(1);
+
+// How many positions a search for the moves played since the engine last moved may visit.
+// Games with many moves, like Amazons, can only be searched a ply or two deep within it.
+const MAX_SEARCH_NODES: usize = 100_000;
+
+// A move as one word: its Display form with whitespace replaced by '_', e.g. "Column_4" or "3_5"
+pub fn move_notation(mv: &impl Display) -> String {
+    mv.to_string().split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+fn same_position<G: Game>(a: &G, b: &G) -> bool {
+    a.get_board() == b.get_board() && a.current_player() == b.current_player()
+}
+
+// The fewest moves leading from one position to another, searching deeper until max_nodes positions were visited
+fn moves_between<G: Game>(from: &G, to: &G, max_nodes: usize) -> Option<Vec<G::Move>> {
+    let mut budget = max_nodes;
+    let mut depth = 0;
+    loop {
+        if let Some(moves) = moves_within(from, to, depth, &mut budget) {
+            return Some(moves);
+        }
+        if budget == 0 {
+            return None;
+        }
+        depth += 1;
+    }
+}
+
+fn moves_within<G: Game>(
+    from: &G,
+    to: &G,
+    depth: usize,
+    budget: &mut usize,
+) -> Option<Vec<G::Move>> {
+    if *budget == 0 {
+        return None;
+    }
+    *budget -= 1;
+
+    if same_position(from, to) {
+        return Some(vec![]);
+    }
+    if depth == 0 {
+        return None;
+    }
+    from.legal_moves().into_iter().find_map(|mv| {
+        let mut next = from.clone();
+        next.make_move(mv);
+        let mut rest = moves_within(&next, to, depth - 1, budget)?;
+        rest.insert(0, mv);
+        Some(rest)
+    })
+}
+
+struct Engine {
+    child: Child,
+    stdin: ChildStdin,
+    // Lines from the engine, read on their own thread so waiting for them can time out
+    lines: Receiver<String>,
+}
+
+impl Engine {
+    fn spawn(command: &str, args: &[String]) -> Result<Engine, String> {
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("Couldn't start {}: {}", command, err))?;
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Engine { child, stdin, lines })
+    }
+
+    fn send(&mut self, line: &str) -> Result<(), String> {
+        writeln!(self.stdin, "{}", line)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|err| format!("Couldn't write to the engine: {}", err))
+    }
+
+    // Waits for a line starting with the given word, skipping anything else the engine prints
+    fn wait_for(&self, word: &str, deadline: Instant) -> Result<String, String> {
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.lines.recv_timeout(remaining) {
+                Ok(line) => {
+                    let mut words = line.split_whitespace();
+                    if words.next() == Some(word) {
+                        return Ok(words.collect::<Vec<_>>().join(" "));
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format!("The engine didn't send {} in time", word))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err("The engine exited".to_string())
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Engine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/*
+Plays through another program, so engines written in any language can join a Leaderboard.
+The program reads commands on stdin and answers on stdout, one per line:
+
+    newgame                         start a new game
+    position startpos moves m1 m2   the moves played so far, in move_notation
+    go 1000                         think for at most this many milliseconds
+    bestmove m                      the engine's answer to go
+    quit                            exit
+
+Anything else the engine prints is ignored. If it crashes or takes too long, the move fails with
+PlayerError::EngineFailure and a fresh process is started for the next one.
+ */
+pub struct External<G: Game> {
+    command: String,
+    args: Vec<String>,
+    think_time: Duration,
+    engine: RefCell<Option<Engine>>,
+    // The position the moves lead to, and the moves played from the start to get there
+    history: RefCell<(G, Vec<G::Move>)>,
+    // The engine's last answer, which is usually played next but may not have been
+    last_answer: RefCell<Option<G::Move>>,
+}
+
+impl<G: Game> External<G> {
+    pub fn new(command: &str, args: Vec<String>, think_time: Duration) -> External<G> {
+        External {
+            command: command.to_string(),
+            args,
+            think_time,
+            engine: RefCell::new(None),
+            history: RefCell::new((G::new(), vec![])),
+            last_answer: RefCell::new(None),
+        }
+    }
+
+    fn update_history(&self, game: &G) -> Result<(), PlayerError> {
+        let mut history = self.history.borrow_mut();
+        let answer = self.last_answer.borrow_mut().take();
+
+        // A new game is spotted before searching, which takes long in games with many moves
+        let start = G::new();
+        if same_position(game, &start) {
+            if answer.is_some() || !history.1.is_empty() {
+                *history = (start, vec![]);
+                self.engine_command("newgame")?;
+            }
+            return Ok(());
+        }
+
+        // Usually the engine's answer was played, so only the opponent's reply needs finding
+        if let Some(answer) = answer {
+            let mut played = history.0.clone();
+            played.make_move(answer);
+            if let Some(replies) = moves_between(&played, game, MAX_SEARCH_NODES) {
+                history.0 = played;
+                history.1.push(answer);
+                for mv in replies {
+                    history.0.make_move(mv);
+                    history.1.push(mv);
+                }
+                return Ok(());
+            }
+        }
+
+        let new_moves = match moves_between(&history.0, game, MAX_SEARCH_NODES) {
+            Some(moves) => moves,
+            // A different game has started
+            None => {
+                *history = (start, vec![]);
+                self.engine_command("newgame")?;
+                moves_between(&history.0, game, MAX_SEARCH_NODES).ok_or_else(|| {
+                    PlayerError::EngineFailure(
+                        "Can't tell which moves led to this position".to_string(),
+                    )
+                })?
+            }
+        };
+
+        for mv in new_moves {
+            history.0.make_move(mv);
+            history.1.push(mv);
+        }
+        Ok(())
+    }
+
+    // Sends a command, starting the engine first if it isn't running
+    fn engine_command(&self, line: &str) -> Result<(), PlayerError> {
+        let mut engine = self.engine.borrow_mut();
+        if engine.is_none() {
+            let mut started =
+                Engine::spawn(&self.command, &self.args).map_err(PlayerError::EngineFailure)?;
+            started.send("newgame").map_err(PlayerError::EngineFailure)?;
+            *engine = Some(started);
+        }
+        let result = engine.as_mut().unwrap().send(line);
+        result.map_err(|err| {
+            *engine = None;
+            PlayerError::EngineFailure(err)
+        })
+    }
+
+    fn ask_engine(&self, game: &G) -> Result<G::Move, PlayerError> {
+        self.update_history(game)?;
+
+        let moves: Vec<String> = self
+            .history
+            .borrow()
+            .1
+            .iter()
+            .map(move_notation)
+            .collect();
+        let position = if moves.is_empty() {
+            "position startpos".to_string()
+        } else {
+            format!("position startpos moves {}", moves.join(" "))
+        };
+        self.engine_command(&position)?;
+        self.engine_command(&format!("go {}", self.think_time.as_millis()))?;
+
+        let deadline = Instant::now() + self.think_time + GRACE;
+        let answer = self
+            .engine
+            .borrow()
+            .as_ref()
+            .unwrap()
+            .wait_for("bestmove", deadline)
+            .map_err(PlayerError::EngineFailure)?;
+
+        let mv = game
+            .legal_moves()
+            .into_iter()
+            .find(|mv| move_notation(mv) == answer)
+            .ok_or_else(|| PlayerError::EngineFailure(format!("{} isn't a legal move", answer)))?;
+        *self.last_answer.borrow_mut() = Some(mv);
+        Ok(mv)
+    }
+}
+
+impl<G: Game + 'static> Player<G> for External<G> {
+    fn choose_move(&self, game: &G) -> Result<<G as Game>::Move, PlayerError> {
+        if game.legal_moves().is_empty() {
+            return Err(PlayerError::NoLegalMoves);
+        }
+
+        let result = self.ask_engine(game);
+        if result.is_err() {
+            // Whatever state the engine is in can't be trusted, so the next move starts it afresh
+            *self.engine.borrow_mut() = None;
+        }
+        result
+    }
+
+    fn reset(&mut self) {
+        *self.history.get_mut() = (G::new(), vec![]);
+        *self.last_answer.get_mut() = None;
+        if self.engine.get_mut().is_some() {
+            let _ = self.engine_command("newgame");
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
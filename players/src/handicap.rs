@@ -0,0 +1,132 @@
+use rand::{seq::SliceRandom, Rng};
+use rust_games_shared::{Game, Player, PlayerError};
+
+use std::marker::PhantomData;
+
+/*
+Plays like the inner player, except that with probability epsilon it plays a random legal move instead.
+Epsilon 0 is the inner player at full strength and 1 is Random.
+ */
+pub struct EpsilonRandom<G: Game, P: Player<G>> {
+    inner: P,
+    epsilon: f32,
+    game: PhantomData<G>,
+}
+
+impl<G: Game, P: Player<G>> EpsilonRandom<G, P> {
+    pub fn new(inner: P, epsilon: f32) -> EpsilonRandom<G, P> {
+        assert!(
+            (0.0..=1.0).contains(&epsilon),
+            "Epsilon is a probability, so between 0 and 1"
+        );
+        EpsilonRandom {
+            inner,
+            epsilon,
+            game: PhantomData,
+        }
+    }
+}
+
+impl<G: Game + 'static, P: Player<G> + 'static> Player<G> for EpsilonRandom<G, P> {
+    fn choose_move(&self, game: &G) -> Result<G::Move, PlayerError> {
+        let mut rng = rand::thread_rng();
+        if rng.gen::<f32>() < self.epsilon {
+            return game
+                .legal_moves()
+                .choose(&mut rng)
+                .copied()
+                .ok_or(PlayerError::NoLegalMoves);
+        }
+        self.inner.choose_move(game)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/*
+Picks uniformly among the inner player's k favourite moves, ranked by its policy. k = 1 plays its most likely move.
+Players without a policy can only name their one choice, so the rest of the k are drawn from the other legal moves.
+ */
+pub struct TopK<G: Game, P: Player<G>> {
+    inner: P,
+    k: usize,
+    game: PhantomData<G>,
+}
+
+impl<G: Game, P: Player<G>> TopK<G, P> {
+    pub fn new(inner: P, k: usize) -> TopK<G, P> {
+        assert!(k > 0, "k must be at least 1");
+        TopK {
+            inner,
+            k,
+            game: PhantomData,
+        }
+    }
+
+    fn ranked_moves(&self, game: &G) -> Result<Vec<G::Move>, PlayerError> {
+        if let Some(mut policy) = self.inner.policy(game) {
+            policy.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+            return Ok(policy.into_iter().map(|(mv, _)| mv).collect());
+        }
+
+        let choice = self.inner.choose_move(game)?;
+        let mut others: Vec<G::Move> = game
+            .legal_moves()
+            .into_iter()
+            .filter(|mv| *mv != choice)
+            .collect();
+        others.shuffle(&mut rand::thread_rng());
+        Ok(std::iter::once(choice).chain(others).collect())
+    }
+}
+
+impl<G: Game + 'static, P: Player<G> + 'static> Player<G> for TopK<G, P> {
+    fn choose_move(&self, game: &G) -> Result<G::Move, PlayerError> {
+        let ranked = self.ranked_moves(game)?;
+        ranked[..ranked.len().min(self.k)]
+            .choose(&mut rand::thread_rng())
+            .copied()
+            .ok_or(PlayerError::NoLegalMoves)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EpsilonRandom, TopK};
+    use crate::TicTacToeSolver;
+    use rust_games_games::TicTacToe;
+    use rust_games_shared::{Game, Player};
+
+    #[test]
+    fn extremes_match_their_players() {
+        let mut g = TicTacToe::new();
+        let moves = TicTacToe::all_possible_moves();
+        // The first player threatens the top row, which the second must block
+        for i in [0, 4, 1] {
+            g.make_move(moves[i]);
+        }
+
+        let solver = TicTacToeSolver::new();
+        let best = solver.choose_move(&g).unwrap();
+        assert_eq!(EpsilonRandom::new(TicTacToeSolver::new(), 0.0).choose_move(&g).unwrap(), best);
+        assert_eq!(TopK::new(TicTacToeSolver::new(), 1).choose_move(&g).unwrap(), best);
+
+        let legal = g.legal_moves();
+        let mv = TopK::new(TicTacToeSolver::new(), 9).choose_move(&g).unwrap();
+        assert!(legal.contains(&mv));
+    }
+}
@@ -1,11 +1,38 @@
 #![allow(incomplete_features)]
+#![allow(type_alias_bounds)]
 #![feature(generic_const_exprs)]
+mod alpha_beta;
+mod by_name;
+mod connect4_solver;
+mod connect4_threats;
 mod corners;
+mod ensemble;
+mod eval_greedy;
+mod evaluate;
+mod external;
 mod greedy;
+mod handicap;
 mod human;
+mod othello_eval;
+mod othello_solver;
 mod random;
+mod tictactoe_solver;
+mod uct;
 
+pub use alpha_beta::{AlphaBeta, SearchBudget};
+pub use by_name::{NamedPlayers, PlayerOptions};
+pub use connect4_solver::{Connect4Solution, Connect4Solver};
+pub use connect4_threats::Connect4Threats;
 pub use corners::Corners;
+pub use ensemble::{Ensemble, Vote};
+pub use eval_greedy::{EvalGreedy, EvalSoftmax};
+pub use evaluate::{AdjudicateByEval, Evaluate, Heuristic, WIN_SCORE};
+pub use external::{move_notation, External};
 pub use greedy::Greedy;
+pub use handicap::{EpsilonRandom, TopK};
 pub use human::Human;
+pub use othello_eval::{OthelloEval, OthelloWeights};
+pub use othello_solver::{OthelloEndgameSolver, OthelloSolution};
 pub use random::Random;
+pub use tictactoe_solver::{SolvedPosition, SolverScore, TicTacToeSolver};
+pub use uct::{Uct, UctBudget};
@@ -0,0 +1,157 @@
+use rust_games_games::Othello;
+use rust_games_shared::{Game, PlayerId};
+
+use crate::{
+    evaluate::Evaluate,
+    othello_solver::{legal_squares, shift, to_bitboards},
+};
+
+// The direction pointing the other way, as numbered by shift
+const OPPOSITE: [usize; 8] = [1, 0, 3, 2, 7, 6, 5, 4];
+
+// What a disc on each square is worth: corners are safe for good, the squares next to them give corners away
+const SQUARE_VALUES: [[f32; 8]; 8] = [
+    [100.0, -20.0, 10.0, 5.0, 5.0, 10.0, -20.0, 100.0],
+    [-20.0, -50.0, -2.0, -2.0, -2.0, -2.0, -50.0, -20.0],
+    [10.0, -2.0, -1.0, -1.0, -1.0, -1.0, -2.0, 10.0],
+    [5.0, -2.0, -1.0, -1.0, -1.0, -1.0, -2.0, 5.0],
+    [5.0, -2.0, -1.0, -1.0, -1.0, -1.0, -2.0, 5.0],
+    [10.0, -2.0, -1.0, -1.0, -1.0, -1.0, -2.0, 10.0],
+    [-20.0, -50.0, -2.0, -2.0, -2.0, -2.0, -50.0, -20.0],
+    [100.0, -20.0, 10.0, 5.0, 5.0, 10.0, -20.0, 100.0],
+];
+
+// How much each feature counts towards the evaluation, each being the player's count minus their opponent's
+#[derive(Debug, Clone, Copy)]
+pub struct OthelloWeights {
+    pub squares: f32,
+    // Moves available right now
+    pub mobility: f32,
+    // Empty squares next to the opponent's discs, where moves may open up later
+    pub potential_mobility: f32,
+    // Discs next to an empty square, which give the opponent moves, so this should be negative
+    pub frontier: f32,
+    // Discs that can never be flipped
+    pub stable: f32,
+    // Whether the player gets the last move, if nobody passes
+    pub parity: f32,
+}
+
+impl Default for OthelloWeights {
+    fn default() -> Self {
+        OthelloWeights {
+            squares: 1.0,
+            mobility: 8.0,
+            potential_mobility: 3.0,
+            frontier: -4.0,
+            stable: 25.0,
+            parity: 10.0,
+        }
+    }
+}
+
+/*
+A classical Othello evaluation, stronger than the rules in Corners and Greedy.
+Plays one ply deep through EvalGreedy, or deeper as the evaluator of AlphaBeta.
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OthelloEval {
+    pub weights: OthelloWeights,
+}
+
+impl OthelloEval {
+    pub fn new(weights: OthelloWeights) -> OthelloEval {
+        OthelloEval { weights }
+    }
+}
+
+// Squares next to at least one of the given ones
+fn neighbours(discs: u64) -> u64 {
+    (0..8).fold(0, |around, direction| around | shift(discs, direction))
+}
+
+// Discs anchored on every line through them by the edge or by other stable discs of the same colour
+fn stable_discs(discs: u64) -> u64 {
+    let mut stable = 0;
+    loop {
+        let mut next = discs;
+        for direction in [0, 2, 4, 5] {
+            let anchored = |direction: usize| {
+                let opposite = OPPOSITE[direction];
+                // Squares whose neighbour that way is off the board, or a stable disc
+                !shift(u64::MAX, opposite) | shift(stable, opposite)
+            };
+            next &= anchored(direction) | anchored(OPPOSITE[direction]);
+        }
+        if next == stable {
+            return stable;
+        }
+        stable = next;
+    }
+}
+
+fn square_total(discs: u64) -> f32 {
+    (0..64)
+        .filter(|square| discs & (1 << square) != 0)
+        .map(|square| SQUARE_VALUES[square / 8][square % 8])
+        .sum()
+}
+
+impl Evaluate<Othello> for OthelloEval {
+    fn evaluate(&self, game: &Othello, player: PlayerId) -> f32 {
+        let (mine, theirs) = to_bitboards(game, player);
+        let empty = !(mine | theirs);
+        let weights = &self.weights;
+
+        let mobility = legal_squares(mine, theirs).count_ones() as f32
+            - legal_squares(theirs, mine).count_ones() as f32;
+        let potential_mobility = (neighbours(theirs) & empty).count_ones() as f32
+            - (neighbours(mine) & empty).count_ones() as f32;
+        let frontier = (mine & neighbours(empty)).count_ones() as f32
+            - (theirs & neighbours(empty)).count_ones() as f32;
+        let stable =
+            stable_discs(mine).count_ones() as f32 - stable_discs(theirs).count_ones() as f32;
+
+        // With an odd number of empty squares left, the player to move plays last
+        let mover_plays_last = empty.count_ones() % 2 == 1;
+        let parity = if mover_plays_last == (game.current_player() == player) {
+            1.0
+        } else {
+            -1.0
+        };
+
+        weights.squares * (square_total(mine) - square_total(theirs))
+            + weights.mobility * mobility
+            + weights.potential_mobility * potential_mobility
+            + weights.frontier * frontier
+            + weights.stable * stable
+            + weights.parity * parity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{stable_discs, OthelloEval};
+    use crate::Evaluate;
+    use rust_games_games::Othello;
+    use rust_games_shared::{Game, PlayerId};
+
+    #[test]
+    fn evaluation_is_zero_sum() {
+        let mut g = Othello::new();
+        g.make_move(g.legal_moves()[0]);
+        let eval = OthelloEval::default();
+        assert_eq!(
+            eval.evaluate(&g, PlayerId::First),
+            -eval.evaluate(&g, PlayerId::Second)
+        );
+    }
+
+    #[test]
+    fn stability_spreads_from_corners() {
+        assert_eq!(stable_discs(u64::MAX).count_ones(), 64);
+        // The top row, and a disc in the middle of the board
+        let discs = 0xff | (1 << 27);
+        assert_eq!(stable_discs(discs), 0xff);
+    }
+}
@@ -0,0 +1,262 @@
+use alphazero::MoveOverride;
+use rust_games_games::{Othello, OthelloMove, OthelloState};
+use rust_games_shared::{Game, Player, PlayerError, PlayerId};
+
+use crate::{AlphaBeta, Heuristic, SearchBudget};
+
+const NOT_FILE_A: u64 = !0x0101_0101_0101_0101;
+const NOT_FILE_H: u64 = !0x8080_8080_8080_8080;
+
+// Below this many empty squares, ordering moves costs more than the pruning saves
+const ORDERING_EMPTIES: u32 = 5;
+
+// Moves every disc one square in one of the eight directions, dropping those that leave the board
+pub(crate) fn shift(discs: u64, direction: usize) -> u64 {
+    match direction {
+        0 => (discs << 1) & NOT_FILE_A,
+        1 => (discs >> 1) & NOT_FILE_H,
+        2 => discs << 8,
+        3 => discs >> 8,
+        4 => (discs << 9) & NOT_FILE_A,
+        5 => (discs << 7) & NOT_FILE_H,
+        6 => (discs >> 7) & NOT_FILE_A,
+        _ => (discs >> 9) & NOT_FILE_H,
+    }
+}
+
+// Squares where the player to move, owning mine, can place a disc
+pub(crate) fn legal_squares(mine: u64, theirs: u64) -> u64 {
+    let empty = !(mine | theirs);
+    let mut squares = 0;
+    for direction in 0..8 {
+        let mut line = shift(mine, direction) & theirs;
+        for _ in 0..5 {
+            line |= shift(line, direction) & theirs;
+        }
+        squares |= shift(line, direction) & empty;
+    }
+    squares
+}
+
+fn flips(square: u64, mine: u64, theirs: u64) -> u64 {
+    let mut flipped = 0;
+    for direction in 0..8 {
+        let mut line = 0;
+        let mut next = shift(square, direction);
+        while next & theirs != 0 {
+            line |= next;
+            next = shift(next, direction);
+        }
+        if next & mine != 0 {
+            flipped |= line;
+        }
+    }
+    flipped
+}
+
+// The 4x4 quadrant a square is in
+fn quadrant(square: u32) -> usize {
+    let (x, y) = (square % 8, square / 8);
+    (x / 4 + 2 * (y / 4)) as usize
+}
+
+// The exact result of an endgame and a move that achieves it
+#[derive(Debug, Clone, Copy)]
+pub struct OthelloSolution {
+    // The final disc count of the player to move minus their opponent's, with perfect play from both
+    pub disc_difference: i32,
+    pub best_move: OthelloMove,
+}
+
+/*
+Solves Othello endgames exactly once at most max_empties squares are left, by alpha-beta search over bitboards.
+Moves that leave the opponent the fewest replies go first, then those in regions with an odd number of empty squares,
+since the last move in a region tends to be worth having. With more empties it plays a shallow heuristic search.
+ */
+pub struct OthelloEndgameSolver {
+    max_empties: usize,
+    fallback: AlphaBeta<Othello, Heuristic>,
+}
+
+impl OthelloEndgameSolver {
+    pub fn new(max_empties: usize) -> OthelloEndgameSolver {
+        OthelloEndgameSolver {
+            max_empties,
+            fallback: AlphaBeta::new(Heuristic, SearchBudget::Depth(4)),
+        }
+    }
+
+    // None if the game is over or has too many empty squares left
+    pub fn solve(&self, game: &Othello) -> Option<OthelloSolution> {
+        if game.is_over() || game.empty_squares() > self.max_empties {
+            return None;
+        }
+
+        let (mine, theirs) = to_bitboards(game, game.current_player());
+        let squares = legal_squares(mine, theirs);
+        if squares == 0 {
+            return Some(OthelloSolution {
+                disc_difference: -negamax(theirs, mine, -64, 64, true),
+                best_move: OthelloMove::Pass,
+            });
+        }
+
+        let mut best = OthelloSolution {
+            disc_difference: -65,
+            best_move: OthelloMove::Pass,
+        };
+        let mut alpha = -64;
+        for square in ordered_moves(mine, theirs, squares) {
+            let flipped = flips(square, mine, theirs);
+            let score = -negamax(theirs ^ flipped, mine | square | flipped, -64, -alpha, false);
+            if score > best.disc_difference {
+                let index = square.trailing_zeros() as usize;
+                best = OthelloSolution {
+                    disc_difference: score,
+                    best_move: OthelloMove::Move(index % 8, index / 8),
+                };
+                alpha = alpha.max(score);
+            }
+        }
+        Some(best)
+    }
+}
+
+// The discs of the given player and of their opponent
+pub(crate) fn to_bitboards(game: &Othello, player: PlayerId) -> (u64, u64) {
+    let mine = match player {
+        PlayerId::First => OthelloState::Black,
+        PlayerId::Second => OthelloState::White,
+    };
+
+    let (mut player, mut opponent) = (0, 0);
+    for (y, row) in game.get_board().iter().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
+            let bit = 1 << (y * 8 + x);
+            if *tile == mine {
+                player |= bit;
+            } else if *tile != OthelloState::Empty {
+                opponent |= bit;
+            }
+        }
+    }
+    (player, opponent)
+}
+
+fn ordered_moves(mine: u64, theirs: u64, mut squares: u64) -> Vec<u64> {
+    let mut moves = vec![];
+    while squares != 0 {
+        moves.push(squares & squares.wrapping_neg());
+        squares &= squares - 1;
+    }
+
+    let empty = !(mine | theirs);
+    if empty.count_ones() < ORDERING_EMPTIES {
+        return moves;
+    }
+
+    let mut region_empties = [0; 4];
+    for square in 0..64 {
+        if empty & (1 << square) != 0 {
+            region_empties[quadrant(square)] += 1;
+        }
+    }
+
+    moves.sort_by_cached_key(|square| {
+        let flipped = flips(*square, mine, theirs);
+        let replies = legal_squares(theirs ^ flipped, mine | square | flipped).count_ones();
+        let even_region = region_empties[quadrant(square.trailing_zeros())] % 2 == 0;
+        (replies, even_region)
+    });
+    moves
+}
+
+// The final disc difference for the player to move, given that the previous player passed or not
+fn negamax(mine: u64, theirs: u64, mut alpha: i32, beta: i32, passed: bool) -> i32 {
+    let squares = legal_squares(mine, theirs);
+    if squares == 0 {
+        if passed {
+            return mine.count_ones() as i32 - theirs.count_ones() as i32;
+        }
+        return -negamax(theirs, mine, -beta, -alpha, true);
+    }
+
+    let mut best = -65;
+    for square in ordered_moves(mine, theirs, squares) {
+        let flipped = flips(square, mine, theirs);
+        let score = -negamax(theirs ^ flipped, mine | square | flipped, -beta, -alpha, false);
+        if score > best {
+            best = score;
+            if score > alpha {
+                alpha = score;
+                if alpha >= beta {
+                    break;
+                }
+            }
+        }
+    }
+    best
+}
+
+impl Player<Othello> for OthelloEndgameSolver {
+    fn choose_move(&self, game: &Othello) -> Result<OthelloMove, PlayerError> {
+        match self.solve(game) {
+            Some(solution) => Ok(solution.best_move),
+            None => self.fallback.choose_move(game),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.fallback.reset();
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl MoveOverride<Othello> for OthelloEndgameSolver {
+    fn override_move(&self, game: &Othello) -> Option<OthelloMove> {
+        self.solve(game).map(|solution| solution.best_move)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OthelloEndgameSolver;
+    use rust_games_games::{Othello, OthelloMove, OthelloState};
+    use rust_games_shared::{Game, GameResult, PlayerId};
+
+    // Black fills the board except for a corner that flips the whole bottom row
+    fn corner_left() -> Othello {
+        let mut board = [[OthelloState::Black; 8]; 8];
+        board[7] = [OthelloState::White; 8];
+        board[7][0] = OthelloState::Empty;
+        board[7][7] = OthelloState::Black;
+        Othello::new_from_board(board, PlayerId::First)
+    }
+
+    #[test]
+    fn takes_the_last_square() {
+        let g = corner_left();
+        let solver = OthelloEndgameSolver::new(10);
+        let solution = solver.solve(&g).unwrap();
+        assert_eq!(solution.best_move, OthelloMove::Move(0, 7));
+        assert_eq!(solution.disc_difference, 64);
+    }
+
+    #[test]
+    fn matches_the_game_result() {
+        let g = corner_left();
+        let solver = OthelloEndgameSolver::new(10);
+        let mut after = g.clone();
+        after.make_move(solver.solve(&g).unwrap().best_move);
+        // White has nothing left to play
+        after.make_move(OthelloMove::Pass);
+        assert!(matches!(
+            after.get_result(),
+            Some(GameResult::Winner(PlayerId::First))
+        ));
+        assert!(OthelloEndgameSolver::new(0).solve(&Othello::new()).is_none());
+    }
+}
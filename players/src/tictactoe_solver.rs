@@ -0,0 +1,229 @@
+use dfdx::prelude::*;
+use rand::seq::SliceRandom;
+use rust_games_games::{TTTMove, TTTState, TicTacToe};
+use rust_games_shared::{Game, GameResult, NnEncoder, Player, PlayerError, PlayerId};
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct SolvedPosition {
+    pub game: TicTacToe,
+    // 1 if the first player wins with perfect play, -1 if the second does and 0 for a draw
+    pub value: f32,
+    // Every move that keeps the value, empty once the game is over
+    pub optimal_moves: Vec<TTTMove>,
+}
+
+// How closely a model's output matches perfect play over the positions that still have moves to make
+#[derive(Debug, Clone, Copy)]
+pub struct SolverScore {
+    pub positions: usize,
+    // Positions where the highest rated legal move is optimal
+    pub policy_accuracy: f32,
+    // Positions where the value rounds to the right result
+    pub value_accuracy: f32,
+    pub value_mse: f32,
+}
+
+/*
+Solves TicTacToe by searching every reachable position once, keeping each one's value and optimal moves.
+Plays perfectly as a Player, and gives the ground truth to measure trained models against.
+ */
+pub struct TicTacToeSolver {
+    table: HashMap<[[TTTState; 3]; 3], SolvedPosition>,
+}
+
+impl TicTacToeSolver {
+    pub fn new() -> TicTacToeSolver {
+        let mut solver = TicTacToeSolver {
+            table: HashMap::new(),
+        };
+        solver.solve(&TicTacToe::new());
+        solver
+    }
+
+    // Whose turn it is follows from the board, so the board alone is the key
+    fn solve(&mut self, game: &TicTacToe) -> f32 {
+        if let Some(solved) = self.table.get(&game.get_board()) {
+            return solved.value;
+        }
+
+        let (value, optimal_moves) = if game.is_over() {
+            let value = match game.get_result() {
+                Some(GameResult::Winner(PlayerId::First)) => 1.0,
+                Some(GameResult::Winner(PlayerId::Second)) => -1.0,
+                _ => 0.0,
+            };
+            (value, vec![])
+        } else {
+            let sign = match game.current_player() {
+                PlayerId::First => 1.0,
+                PlayerId::Second => -1.0,
+            };
+            let children: Vec<(TTTMove, f32)> = game
+                .legal_moves()
+                .into_iter()
+                .map(|mv| {
+                    let mut next = game.clone();
+                    next.make_move(mv);
+                    (mv, self.solve(&next))
+                })
+                .collect();
+
+            let best = children
+                .iter()
+                .map(|(_, value)| sign * value)
+                .fold(f32::NEG_INFINITY, f32::max);
+            let optimal_moves = children
+                .iter()
+                .filter(|(_, value)| sign * value == best)
+                .map(|(mv, _)| *mv)
+                .collect();
+            (sign * best, optimal_moves)
+        };
+
+        self.table.insert(
+            game.get_board(),
+            SolvedPosition {
+                game: game.clone(),
+                value,
+                optimal_moves,
+            },
+        );
+        value
+    }
+
+    pub fn lookup(&self, game: &TicTacToe) -> &SolvedPosition {
+        self.table
+            .get(&game.get_board())
+            .expect("Every reachable position is in the table")
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &SolvedPosition> {
+        self.table.values()
+    }
+
+    /*
+    Scores a model given as a closure from a position to its policy, indexed like all_possible_moves,
+    and its value from PlayerId::First's perspective.
+     */
+    pub fn score(&self, mut predict: impl FnMut(&TicTacToe) -> (Vec<f32>, f32)) -> SolverScore {
+        let moves = TicTacToe::all_possible_moves();
+        let mut positions = 0;
+        let mut policy_hits = 0;
+        let mut value_hits = 0;
+        let mut squared_error = 0.0;
+
+        for solved in self.positions().filter(|solved| !solved.optimal_moves.is_empty()) {
+            let (policy, value) = predict(&solved.game);
+            positions += 1;
+
+            let legal = solved.game.legal_moves();
+            let chosen = (0..moves.len())
+                .filter(|i| legal.contains(&moves[*i]))
+                .max_by(|a, b| policy[*a].total_cmp(&policy[*b]))
+                .unwrap();
+            if solved.optimal_moves.contains(&moves[chosen]) {
+                policy_hits += 1;
+            }
+
+            if value.round().clamp(-1.0, 1.0) == solved.value {
+                value_hits += 1;
+            }
+            squared_error += (value - solved.value).powi(2);
+        }
+
+        SolverScore {
+            positions,
+            policy_accuracy: policy_hits as f32 / positions as f32,
+            value_accuracy: value_hits as f32 / positions as f32,
+            value_mse: squared_error / positions as f32,
+        }
+    }
+
+    // Scores an AlphaZero network, which values a canonical position for the player to move
+    pub fn score_network(
+        &self,
+        model: &impl Module<
+            Tensor<(Const<3>, Const<3>, Const<3>), f32, AutoDevice>,
+            Output = (
+                Tensor<(Const<9>,), f32, AutoDevice>,
+                Tensor<(Const<1>,), f32, AutoDevice>,
+            ),
+            Error = <AutoDevice as HasErr>::Err,
+        >,
+        canonical: bool,
+    ) -> SolverScore {
+        let dev: AutoDevice = Default::default();
+        let mut encoder: NnEncoder<TicTacToe, AutoDevice> = NnEncoder::new(&dev);
+
+        self.score(|game| {
+            let (p, v) = model.forward(encoder.encode(game, canonical));
+            let value = match (canonical, game.current_player()) {
+                (true, PlayerId::Second) => -v.array()[0],
+                _ => v.array()[0],
+            };
+            (p.as_vec(), value)
+        })
+    }
+}
+
+impl Default for TicTacToeSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player<TicTacToe> for TicTacToeSolver {
+    fn choose_move(&self, game: &TicTacToe) -> Result<TTTMove, PlayerError> {
+        match self.lookup(game).optimal_moves.choose(&mut rand::thread_rng()) {
+            Some(mv) => Ok(*mv),
+            None => Err(PlayerError::NoLegalMoves),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TicTacToeSolver;
+    use crate::Random;
+    use rust_games_games::TicTacToe;
+    use rust_games_shared::{Game, GameResult, PlayerId, Strategy};
+
+    #[test]
+    fn perfect_play_is_a_draw() {
+        let solver = TicTacToeSolver::new();
+        assert_eq!(solver.lookup(&TicTacToe::new()).value, 0.0);
+        assert_eq!(solver.positions().count(), 5478);
+    }
+
+    #[test]
+    fn never_loses_to_random() {
+        let solver = Strategy::new("Solver".to_string(), TicTacToeSolver::new());
+        let random = Strategy::new("Random".to_string(), Random::new());
+        for _ in 0..20 {
+            let result = TicTacToe::play_full_game(vec![&random, &solver], false);
+            assert!(!matches!(result, GameResult::Winner(PlayerId::First)));
+        }
+    }
+
+    #[test]
+    fn the_table_scores_itself_perfectly() {
+        let solver = TicTacToeSolver::new();
+        let moves = TicTacToe::all_possible_moves();
+        let score = solver.score(|game| {
+            let solved = solver.lookup(game);
+            let policy = moves
+                .iter()
+                .map(|mv| if solved.optimal_moves.contains(mv) { 1.0 } else { 0.0 })
+                .collect();
+            (policy, solved.value)
+        });
+        assert_eq!(score.policy_accuracy, 1.0);
+        assert_eq!(score.value_accuracy, 1.0);
+    }
+}
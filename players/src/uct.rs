@@ -0,0 +1,242 @@
+use rand::seq::SliceRandom;
+use rust_games_shared::{Game, GameResult, Player, PlayerError, PlayerId};
+
+use std::time::{Duration, Instant};
+
+use crate::Random;
+
+#[derive(Debug, Clone, Copy)]
+pub enum UctBudget {
+    Iterations(usize),
+    Time(Duration),
+}
+
+struct Node<M> {
+    mv: Option<M>,
+    // Who chose the move leading here, whose wins the node counts. None for the root and chance outcomes
+    mover: Option<PlayerId>,
+    children: Vec<usize>,
+    untried: Vec<M>,
+    visits: f32,
+    wins: f32,
+}
+
+impl<M> Node<M> {
+    fn new<G: Game<Move = M>>(mv: Option<M>, mover: Option<PlayerId>, game: &G) -> Node<M> {
+        let mut untried = if game.is_over() || game.is_chance_node() {
+            vec![]
+        } else {
+            game.legal_moves()
+        };
+        untried.shuffle(&mut rand::thread_rng());
+
+        Node {
+            mv,
+            mover,
+            children: vec![],
+            untried,
+            visits: 0.0,
+            wins: 0.0,
+        }
+    }
+}
+
+// 1 for a win, 0 for a loss and a half for anything in between
+fn reward(result: &Option<GameResult>, player: PlayerId) -> f32 {
+    match result {
+        Some(GameResult::Winner(winner)) if *winner == player => 1.0,
+        Some(GameResult::Winner(_)) => 0.0,
+        Some(GameResult::Ranking(ranking)) => match ranking.iter().position(|p| *p == player) {
+            Some(place) if ranking.len() > 1 => 1.0 - place as f32 / (ranking.len() - 1) as f32,
+            _ => 0.5,
+        },
+        Some(GameResult::Tie) | None => 0.5,
+    }
+}
+
+/*
+Plain Monte Carlo tree search, with no network: children are picked by UCB1 and new leaves are valued by playing
+the game out with the rollout player, which is Random unless a heuristic player such as EvalSoftmax is given.
+One iteration adds one node, so an iteration budget compares directly with AlphaZero's traversal_iter.
+ */
+pub struct Uct<G: Game, P: Player<G> = Random<G>> {
+    rollout: P,
+    budget: UctBudget,
+    exploration: f32,
+    game: std::marker::PhantomData<G>,
+}
+
+impl<G: Game + 'static> Uct<G, Random<G>> {
+    pub fn new(budget: UctBudget) -> Uct<G, Random<G>> {
+        Uct::with_rollout(Random::new(), budget)
+    }
+}
+
+impl<G: Game, P: Player<G>> Uct<G, P> {
+    pub fn with_rollout(rollout: P, budget: UctBudget) -> Uct<G, P> {
+        Uct {
+            rollout,
+            budget,
+            exploration: std::f32::consts::SQRT_2,
+            game: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_exploration(mut self, exploration: f32) -> Uct<G, P> {
+        self.exploration = exploration;
+        self
+    }
+
+    fn select_child(&self, nodes: &[Node<G::Move>], parent: usize) -> usize {
+        let log_visits = nodes[parent].visits.ln();
+        let ucb = |child: usize| {
+            let node = &nodes[child];
+            node.wins / node.visits + self.exploration * (log_visits / node.visits).sqrt()
+        };
+
+        *nodes[parent]
+            .children
+            .iter()
+            .max_by(|a, b| ucb(**a).total_cmp(&ucb(**b)))
+            .unwrap()
+    }
+
+    fn iterate(&self, nodes: &mut Vec<Node<G::Move>>, root: &G) {
+        let mut game = root.clone();
+        let mut path = vec![0];
+        let mut current = 0;
+
+        // Selection and expansion
+        while !game.is_over() {
+            if game.is_chance_node() {
+                let outcome = game.sample_chance();
+                game.make_move(outcome);
+                let existing = nodes[current]
+                    .children
+                    .iter()
+                    .copied()
+                    .find(|child| nodes[*child].mv == Some(outcome));
+                current = existing.unwrap_or_else(|| {
+                    nodes.push(Node::new(Some(outcome), None, &game));
+                    let child = nodes.len() - 1;
+                    nodes[current].children.push(child);
+                    child
+                });
+                path.push(current);
+                continue;
+            }
+
+            let mover = game.current_player();
+            if let Some(mv) = nodes[current].untried.pop() {
+                game.make_move(mv);
+                nodes.push(Node::new(Some(mv), Some(mover), &game));
+                let child = nodes.len() - 1;
+                nodes[current].children.push(child);
+                path.push(child);
+                break;
+            }
+
+            if nodes[current].children.is_empty() {
+                break;
+            }
+            current = self.select_child(nodes, current);
+            game.make_move(nodes[current].mv.unwrap());
+            path.push(current);
+        }
+
+        // Simulation
+        while !game.is_over() {
+            let mv = if game.is_chance_node() {
+                game.sample_chance()
+            } else {
+                match self.rollout.choose_move(&game) {
+                    Ok(mv) => mv,
+                    Err(_) => break,
+                }
+            };
+            game.make_move(mv);
+        }
+
+        // Backpropagation
+        let result = game.get_result();
+        for index in path {
+            let node = &mut nodes[index];
+            node.visits += 1.0;
+            if let Some(mover) = node.mover {
+                node.wins += reward(&result, mover);
+            }
+        }
+    }
+}
+
+impl<G: Game + 'static, P: Player<G> + 'static> Player<G> for Uct<G, P> {
+    fn choose_move(&self, game: &G) -> Result<<G as Game>::Move, PlayerError> {
+        let mut nodes = vec![Node::new(None, None, game)];
+        if nodes[0].untried.is_empty() {
+            return Err(PlayerError::NoLegalMoves);
+        }
+
+        match self.budget {
+            UctBudget::Iterations(iterations) => {
+                for _ in 0..iterations {
+                    self.iterate(&mut nodes, game);
+                }
+            }
+            UctBudget::Time(time) => {
+                let deadline = Instant::now() + time;
+                while Instant::now() < deadline {
+                    self.iterate(&mut nodes, game);
+                }
+            }
+        }
+
+        // The most visited move is the most robust choice
+        match nodes[0]
+            .children
+            .iter()
+            .max_by(|a, b| nodes[**a].visits.total_cmp(&nodes[**b].visits))
+        {
+            Some(child) => Ok(nodes[*child].mv.unwrap()),
+            None => Ok(nodes[0].untried[0]),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.rollout.reset();
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Uct, UctBudget};
+    use crate::{EvalSoftmax, Heuristic};
+    use rust_games_games::Connect4;
+    use rust_games_shared::{Game, Player};
+
+    fn three_in_first_column() -> Connect4 {
+        let mut g = Connect4::new();
+        let moves = Connect4::all_possible_moves();
+        for column in [0, 1, 0, 1, 0, 1] {
+            g.make_move(moves[column]);
+        }
+        g
+    }
+
+    #[test]
+    fn random_rollouts_take_the_win() {
+        let g = three_in_first_column();
+        let player = Uct::new(UctBudget::Iterations(500));
+        assert_eq!(player.choose_move(&g).unwrap(), Connect4::all_possible_moves()[0]);
+    }
+
+    #[test]
+    fn heuristic_rollouts_take_the_win() {
+        let g = three_in_first_column();
+        let player = Uct::with_rollout(EvalSoftmax::new(Heuristic, 1.0), UctBudget::Iterations(200));
+        assert_eq!(player.choose_move(&g).unwrap(), Connect4::all_possible_moves()[0]);
+    }
+}
@@ -0,0 +1,67 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use rust_games_games::Connect4;
+use rust_games_players::{External, Random};
+use rust_games_shared::{Game, Player, PlayerError, PlayerId};
+
+use std::time::Duration;
+
+fn mock_engine(mode: &str) -> External<Connect4> {
+    External::new(
+        env!("CARGO_BIN_EXE_mock_engine"),
+        vec![mode.to_string()],
+        Duration::from_millis(100),
+    )
+}
+
+#[test]
+fn plays_a_full_game() {
+    let engine = mock_engine("play");
+    let random = Random::new();
+    let mut g = Connect4::new();
+
+    while !g.is_over() {
+        let mv = match g.current_player() {
+            PlayerId::First => engine.choose_move(&g).unwrap(),
+            PlayerId::Second => random.choose_move(&g).unwrap(),
+        };
+        g.make_move(mv);
+    }
+}
+
+#[test]
+fn crashes_and_hangs_are_errors() {
+    let g = Connect4::new();
+    for mode in ["crash", "hang"] {
+        assert!(matches!(
+            mock_engine(mode).choose_move(&g),
+            Err(PlayerError::EngineFailure(_))
+        ));
+    }
+}
+
+#[test]
+fn new_games_and_unknown_positions() {
+    let engine = mock_engine("play");
+    let random = Random::new();
+    // Too short for anyone to connect four
+    let mut g = Connect4::new();
+    for _ in 0..3 {
+        g.make_move(engine.choose_move(&g).unwrap());
+        g.make_move(random.choose_move(&g).unwrap());
+    }
+
+    // Starting over is spotted without a search
+    assert!(engine.choose_move(&Connect4::new()).is_ok());
+
+    // Moves the engine never saw are only searched for up to a node budget, far less than 10 plies of Connect4
+    let mut unknown = Connect4::new();
+    for ply in 0..10 {
+        unknown.make_move(unknown.legal_moves()[ply % 7]);
+    }
+    assert!(matches!(
+        engine.choose_move(&unknown),
+        Err(PlayerError::EngineFailure(_))
+    ));
+}
@@ -0,0 +1,127 @@
+use dfdx::prelude::*;
+
+use crate::Game;
+
+// Many positions, as (batch, CHANNELS, X, Y)
+type BatchShape<G: Game> = (usize, Const<{ G::CHANNELS }>, G::BoardSizeX, G::BoardSizeY);
+
+/*
+Encodes positions into tensors on a device chosen by the caller, reusing the same buffers between calls.
+Many positions become one (batch, CHANNELS, X, Y) tensor, so they can be evaluated in a single forward pass.
+ */
+pub struct NnEncoder<G: Game, D: Device<f32>>
+where
+    [(); G::CHANNELS]: Sized,
+{
+    dev: D,
+    buffer: Vec<f32>,
+    single: Tensor<(Const<{ G::CHANNELS }>, G::BoardSizeX, G::BoardSizeY), f32, D>,
+    batch: Tensor<BatchShape<G>, f32, D>,
+}
+
+impl<G: Game, D: Device<f32>> NnEncoder<G, D>
+where
+    [(); G::CHANNELS]: Sized,
+{
+    pub fn new(dev: &D) -> Self {
+        Self {
+            dev: dev.clone(),
+            buffer: Vec::with_capacity(G::nn_input_len()),
+            single: dev.zeros_like(&(
+                Const::<{ G::CHANNELS }>,
+                G::BoardSizeX::default(),
+                G::BoardSizeY::default(),
+            )),
+            batch: dev.zeros_like(&(
+                0,
+                Const::<{ G::CHANNELS }>,
+                G::BoardSizeX::default(),
+                G::BoardSizeY::default(),
+            )),
+        }
+    }
+
+    pub fn encode(
+        &mut self,
+        game: &G,
+        canonical: bool,
+    ) -> Tensor<(Const<{ G::CHANNELS }>, G::BoardSizeX, G::BoardSizeY), f32, D> {
+        self.buffer.clear();
+        self.buffer.resize(G::nn_input_len(), 0.0);
+        Self::write(game, canonical, &mut self.buffer);
+
+        self.single.copy_from(&self.buffer);
+        self.single.clone()
+    }
+
+    // Loads a position that was already written with Game::write_nn_input, e.g. a stored training example
+    pub fn from_encoded(
+        &mut self,
+        position: &[f32],
+    ) -> Tensor<(Const<{ G::CHANNELS }>, G::BoardSizeX, G::BoardSizeY), f32, D> {
+        self.single.copy_from(position);
+        self.single.clone()
+    }
+
+    pub fn encode_batch<'a>(
+        &mut self,
+        games: impl IntoIterator<Item = &'a G>,
+        canonical: bool,
+    ) -> Tensor<BatchShape<G>, f32, D>
+    where
+        G: 'a,
+    {
+        let len = G::nn_input_len();
+        self.buffer.clear();
+
+        let mut batch_size = 0;
+        for game in games {
+            self.buffer.resize((batch_size + 1) * len, 0.0);
+            Self::write(game, canonical, &mut self.buffer[batch_size * len..]);
+            batch_size += 1;
+        }
+
+        self.fill_batch(batch_size)
+    }
+
+    pub fn batch_from_encoded<'a>(
+        &mut self,
+        positions: impl IntoIterator<Item = &'a [f32]>,
+    ) -> Tensor<BatchShape<G>, f32, D> {
+        self.buffer.clear();
+
+        let mut batch_size = 0;
+        for position in positions {
+            assert_eq!(position.len(), G::nn_input_len());
+            self.buffer.extend_from_slice(position);
+            batch_size += 1;
+        }
+
+        self.fill_batch(batch_size)
+    }
+
+    fn write(game: &G, canonical: bool, out: &mut [f32]) {
+        if canonical {
+            game.write_canonical_nn_input(out);
+        } else {
+            game.write_nn_input(out);
+        }
+    }
+
+    // Copies the buffer into the batch tensor, which is only reallocated when the batch size changes
+    fn fill_batch(
+        &mut self,
+        batch_size: usize,
+    ) -> Tensor<BatchShape<G>, f32, D> {
+        if self.batch.shape().0 != batch_size {
+            self.batch = self.dev.zeros_like(&(
+                batch_size,
+                Const::<{ G::CHANNELS }>,
+                G::BoardSizeX::default(),
+                G::BoardSizeY::default(),
+            ));
+        }
+        self.batch.copy_from(&self.buffer);
+        self.batch.clone()
+    }
+}
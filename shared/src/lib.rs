@@ -1,4 +1,5 @@
 #![allow(incomplete_features)]
+#![allow(type_alias_bounds)]
 #![feature(generic_const_exprs)]
 
 use std::{
@@ -9,7 +10,12 @@ use std::{
     rc::Rc,
 };
 
-use dfdx::prelude::{Tensor, ConstDim, AutoDevice, Const};
+use dfdx::prelude::{Tensor, ConstDim, AutoDevice, Const, TensorFromVec};
+use rand::{distributions::WeightedIndex, prelude::Distribution};
+
+mod encoder;
+
+pub use encoder::NnEncoder;
 
 #[derive(Debug)]
 pub enum GameResult {
@@ -25,6 +31,15 @@ pub enum PlayerId {
     Second,
 }
 
+impl PlayerId {
+    pub fn opponent(&self) -> PlayerId {
+        match self {
+            PlayerId::First => PlayerId::Second,
+            PlayerId::Second => PlayerId::First,
+        }
+    }
+}
+
 impl From<PlayerId> for usize {
     fn from(value: PlayerId) -> Self {
         match value {
@@ -36,7 +51,8 @@ impl From<PlayerId> for usize {
 
 pub trait Game: Clone + Debug {
     type Move: Copy + Debug + Display + Hash + Eq;
-    type Board: Eq;
+    // Everything that decides the rest of the game apart from the player to move, since searches key their tables on it
+    type Board: Eq + Hash;
 
     type BoardSizeX: ConstDim;
     type BoardSizeY: ConstDim;
@@ -45,10 +61,36 @@ pub trait Game: Clone + Debug {
     const CHANNELS: usize;
     const NUM_PLAYERS: usize;
     const TOTAL_MOVES: usize;
+    // Whether every player sees the whole state; games where they don't also implement ImperfectInformation
+    const PERFECT_INFORMATION: bool = true;
 
     fn new() -> Self;
     fn print(&self);
-    fn to_nn_input(&self) -> Tensor<(Const<{Self::CHANNELS}>, Self::BoardSizeX, Self::BoardSizeY), f32, AutoDevice>;
+    // Writes the network input into out, which starts zeroed and holds nn_input_len values laid out as (CHANNELS, X, Y)
+    fn write_nn_input(&self, out: &mut [f32]);
+    // The position as seen by the given player: their pieces in channel 0, the opponent's in channel 1
+    fn write_perspective_nn_input(&self, player: PlayerId, out: &mut [f32]);
+    // The position as seen by the player to move
+    fn write_canonical_nn_input(&self, out: &mut [f32]) {
+        self.write_perspective_nn_input(self.current_player(), out)
+    }
+    fn nn_input_len() -> usize {
+        Self::CHANNELS * <Self::BoardSizeX as ConstDim>::SIZE * <Self::BoardSizeY as ConstDim>::SIZE
+    }
+
+    // Convenience encodings of a single position; use an NnEncoder to encode many on a chosen device
+    fn to_nn_input(&self) -> Tensor<(Const<{Self::CHANNELS}>, Self::BoardSizeX, Self::BoardSizeY), f32, AutoDevice> {
+        let dev: AutoDevice = Default::default();
+        let mut data = vec![0.0; Self::nn_input_len()];
+        self.write_nn_input(&mut data);
+        dev.tensor_from_vec(data, Default::default())
+    }
+    fn to_canonical_nn_input(&self) -> Tensor<(Const<{Self::CHANNELS}>, Self::BoardSizeX, Self::BoardSizeY), f32, AutoDevice> {
+        let dev: AutoDevice = Default::default();
+        let mut data = vec![0.0; Self::nn_input_len()];
+        self.write_canonical_nn_input(&mut data);
+        dev.tensor_from_vec(data, Default::default())
+    }
     fn get_board(&self) -> Self::Board;
     fn legal_moves(&self) -> Vec<Self::Move>;
     fn make_move(&mut self, m: Self::Move);
@@ -56,6 +98,30 @@ pub trait Game: Clone + Debug {
     fn get_result(&self) -> Option<GameResult>;
     fn current_player(&self) -> PlayerId;
 
+    /*
+    Score difference from PlayerId::First's perspective, for games decided on points.
+    It's informational: MCTS values and Leaderboard ratings only count the win, loss or tie from get_result,
+    and the margin is only read where a finer signal is wanted, like AdjudicateByScore.
+     */
+    fn score_margin(&self) -> Option<f32> {
+        None
+    }
+
+    // Chance events such as dice rolls are moves made by nature rather than by a player.
+    // At a chance node, legal_moves lists the possible outcomes and chance_outcomes gives their probabilities.
+    fn is_chance_node(&self) -> bool {
+        false
+    }
+    fn chance_outcomes(&self) -> Vec<(Self::Move, f32)> {
+        vec![]
+    }
+    fn sample_chance(&self) -> Self::Move {
+        let outcomes = self.chance_outcomes();
+        let dist = WeightedIndex::new(outcomes.iter().map(|(_, prob)| *prob))
+            .expect("A chance node must have outcomes with positive probability");
+        outcomes[dist.sample(&mut rand::thread_rng())].0
+    }
+
     fn all_possible_moves() -> [Self::Move; Self::TOTAL_MOVES];
 
     fn associate_players(players: Vec<&Strategy<Self>>) -> HashMap<PlayerId, &Strategy<Self>>;
@@ -72,6 +138,12 @@ pub trait Game: Clone + Debug {
                 game.print();
             }
 
+            // Nature moves at chance nodes, not the players
+            if game.is_chance_node() {
+                game.make_move(game.sample_chance());
+                continue;
+            }
+
             let current_player = game.current_player();
             let next_move = (*player_map.get(&current_player).unwrap())
                 .player
@@ -95,14 +167,41 @@ pub trait Game: Clone + Debug {
     }
 }
 
+/*
+Games with hidden information, such as the cards in each player's hand.
+The Game methods see the true state, while an observation is only what one player is allowed to know.
+Such games should make write_nn_input encode the current player's observation, so a network never sees hidden state.
+ */
+pub trait ImperfectInformation: Game {
+    type Observation: Clone + Debug + Eq + Hash;
+
+    fn observation(&self, player: PlayerId) -> Self::Observation;
+    fn write_observation_nn_input(&self, player: PlayerId, out: &mut [f32]);
+    fn observation_nn_input(
+        &self,
+        player: PlayerId,
+    ) -> Tensor<(Const<{ Self::CHANNELS }>, Self::BoardSizeX, Self::BoardSizeY), f32, AutoDevice> {
+        let dev: AutoDevice = Default::default();
+        let mut data = vec![0.0; Self::nn_input_len()];
+        self.write_observation_nn_input(player, &mut data);
+        dev.tensor_from_vec(data, Default::default())
+    }
+}
+
 #[derive(Debug)]
 pub enum PlayerError {
     NoLegalMoves,
+    // An external engine crashed, timed out or answered with something that isn't a legal move
+    EngineFailure(String),
 }
 
 pub trait Player<G: Game> {
     fn choose_move(&self, game: &G) -> Result<G::Move, PlayerError>;
     fn reset(&mut self) {}
+    // How likely the player is to choose each legal move, for players that can say. The probabilities sum to 1
+    fn policy(&self, _game: &G) -> Option<Vec<(G::Move, f32)>> {
+        None
+    }
     fn as_any(&self) -> &dyn Any;
 }
 